@@ -0,0 +1,83 @@
+use crate::IVector3;
+
+/// One of the three axes of the world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// The x axis, going east
+    X,
+    /// The y axis, going up
+    Y,
+    /// The z axis, going south
+    Z,
+}
+
+impl Axis {
+    /// A vector of length one pointing in the positive direction of the axis
+    ///
+    /// ```
+    /// # use litematic_editor::{Axis, Vector3};
+    /// assert_eq!(Axis::Y.unit_vector(), Vector3::new(0, 1, 0));
+    /// ```
+    pub fn unit_vector(self) -> IVector3 {
+        match self {
+            Axis::X => IVector3::new(1, 0, 0),
+            Axis::Y => IVector3::new(0, 1, 0),
+            Axis::Z => IVector3::new(0, 0, 1),
+        }
+    }
+
+    /// Parse the value of an `axis` block state property, like the one logs and chains have
+    ///
+    /// Returns `None` if the value isn't `x`, `y`, or `z`
+    ///
+    /// ```
+    /// # use litematic_editor::{Axis, BlockState};
+    /// let log: BlockState = "oak_log[axis=z]".parse().unwrap();
+    ///
+    /// assert_eq!(Axis::from_property_value(&log.properties["axis"]), Some(Axis::Z));
+    /// assert_eq!(Axis::from_property_value("north"), None);
+    /// ```
+    pub fn from_property_value(value: &str) -> Option<Axis> {
+        match value {
+            "x" => Some(Axis::X),
+            "y" => Some(Axis::Y),
+            "z" => Some(Axis::Z),
+            _ => None,
+        }
+    }
+
+    /// The value of an `axis` block state property along this axis
+    pub fn property_value(self) -> &'static str {
+        match self {
+            Axis::X => "x",
+            Axis::Y => "y",
+            Axis::Z => "z",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Axis, Vector3};
+
+    #[test]
+    fn test_unit_vector() {
+        assert_eq!(Axis::X.unit_vector(), Vector3::new(1, 0, 0));
+        assert_eq!(Axis::Y.unit_vector(), Vector3::new(0, 1, 0));
+        assert_eq!(Axis::Z.unit_vector(), Vector3::new(0, 0, 1));
+
+        let sum = Axis::X.unit_vector() + Axis::Y.unit_vector() + Axis::Z.unit_vector();
+
+        assert_eq!(sum, Vector3::new(1, 1, 1));
+    }
+
+    #[test]
+    fn test_property_value() {
+        for axis in [Axis::X, Axis::Y, Axis::Z] {
+            assert_eq!(Axis::from_property_value(axis.property_value()), Some(axis));
+        }
+
+        assert_eq!(Axis::from_property_value("X"), None);
+        assert_eq!(Axis::from_property_value(""), None);
+    }
+}
@@ -1,9 +1,71 @@
-use std::collections::HashMap;
+use std::{cmp::Ordering, collections::HashMap, fmt, str::FromStr, sync::OnceLock};
 
 use core::hash::Hash;
 use quartz_nbt::{NbtCompound, NbtTag};
 
-use crate::BlockStateParseError;
+use crate::{Axis, BlockStateParseError};
+
+/// Properties whose values are always one of a fixed set of lowercase words, which [BlockState::normalize_properties()] lowercases
+///
+/// Covers directions and orientations, shapes and halves, boolean flags, and the sides of walls, fences, and redstone wire. Properties with free-form or numeric values like `age`, `power`, or `note` aren't included
+const ENUM_PROPERTIES: &[&str] = &[
+    // Directions and orientations
+    "axis",
+    "facing",
+    "face",
+    "hinge",
+    "orientation",
+    "rotation_direction",
+    "vertical_direction",
+    // Shapes and parts
+    "attachment",
+    "half",
+    "leaves",
+    "mode",
+    "part",
+    "shape",
+    "thickness",
+    "tilt",
+    "type",
+    "instrument",
+    "sculk_sensor_phase",
+    // Sides of walls, fences, panes, and redstone wire
+    "north",
+    "east",
+    "south",
+    "west",
+    "up",
+    "down",
+    // Boolean flags
+    "attached",
+    "bottom",
+    "conditional",
+    "disarmed",
+    "drag",
+    "enabled",
+    "extended",
+    "eye",
+    "hanging",
+    "has_book",
+    "has_bottle_0",
+    "has_bottle_1",
+    "has_bottle_2",
+    "has_record",
+    "in_wall",
+    "inverted",
+    "lit",
+    "locked",
+    "occupied",
+    "open",
+    "persistent",
+    "powered",
+    "short",
+    "signal_fire",
+    "snowy",
+    "triggered",
+    "unstable",
+    "waterlogged",
+];
 
 /// A struct that represents a block state
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -44,6 +106,64 @@ impl BlockState {
         }
     }
 
+    /// Create a new block state like [new()](BlockState::new), but fail if the block id isn't valid
+    ///
+    /// After being prefixed and converted to lowercase, the id has to be a namespace and a path separated by a colon, made of lowercase letters, digits, and `_-.`, with the path also allowing `/`. Typos like `minecraft::stone` or ids with spaces would otherwise only be caught when minecraft loads the schematic
+    ///
+    /// ```
+    /// # use litematic_editor::{BlockState, BlockStateParseError};
+    /// assert_eq!(BlockState::try_new("Stone", None).unwrap(), BlockState::new("minecraft:stone", None));
+    ///
+    /// assert!(matches!(
+    ///     BlockState::try_new("minecraft::stone", None),
+    ///     Err(BlockStateParseError::InvalidId(id)) if id == "minecraft::stone"
+    /// ));
+    /// ```
+    pub fn try_new(
+        block: &str,
+        properties: Option<HashMap<String, String>>,
+    ) -> Result<BlockState, BlockStateParseError> {
+        let state = BlockState::new(block, properties);
+
+        if !state.has_valid_id() {
+            return Err(BlockStateParseError::InvalidId(state.block));
+        }
+
+        Ok(state)
+    }
+
+    /// Create a block state from a name and a slice of properties, so the properties don't need to be put in a `HashMap` first
+    ///
+    /// The block name is prefixed and converted to lowercase the same way as in [new()](BlockState::new). If a property is given more than once, the last value is used
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// let furnace = BlockState::from_parts("furnace", &[("facing", "north"), ("lit", "true")]);
+    ///
+    /// assert_eq!(furnace, BlockState::new("furnace", None).with_property("facing", "north").with_property("lit", "true"));
+    /// ```
+    pub fn from_parts(block: &str, properties: &[(&str, &str)]) -> BlockState {
+        BlockState {
+            block: BlockState::prefix_block_name(block),
+            properties: properties
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    /// A shared `minecraft:air` block state, so checking for air doesn't need to create a new block state each time
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// assert_eq!(BlockState::air(), &BlockState::new("air", None));
+    /// ```
+    pub fn air() -> &'static BlockState {
+        static AIR: OnceLock<BlockState> = OnceLock::new();
+
+        AIR.get_or_init(|| BlockState::new("air", None))
+    }
+
     /// Gets the name of the block
     ///
     /// ```
@@ -67,6 +187,112 @@ impl BlockState {
         self.block = BlockState::prefix_block_name(block)
     }
 
+    /// Set one of the block's properties, returning the block state so calls can be chained
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// let furnace = BlockState::new("furnace", None)
+    ///     .with_property("facing", "north")
+    ///     .with_property("lit", "true");
+    ///
+    /// assert_eq!(furnace.to_string(), "minecraft:furnace[facing=north,lit=true]");
+    /// ```
+    pub fn with_property(mut self, key: &str, value: &str) -> Self {
+        self.properties.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Check if two block states are the same block, ignoring their properties
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// let furnace = BlockState::new("furnace", None).with_property("facing", "north");
+    ///
+    /// assert!(furnace.same_block(&BlockState::new("furnace", None).with_property("facing", "east")));
+    /// assert!(!furnace.same_block(&BlockState::new("blast_furnace", None)));
+    /// ```
+    pub fn same_block(&self, other: &BlockState) -> bool {
+        self.block == other.block
+    }
+
+    /// Check if the block has the given name, ignoring its properties
+    ///
+    /// The name is prefixed with `minecraft:` if it's left out, and converted to lowercase, the same way as in [new()](BlockState::new)
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// let furnace = BlockState::new("furnace", None).with_property("facing", "north");
+    ///
+    /// assert!(furnace.matches_name("furnace"));
+    /// assert!(furnace.matches_name("minecraft:Furnace"));
+    /// assert!(!furnace.matches_name("coolmod:furnace"));
+    /// ```
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.block == BlockState::prefix_block_name(name)
+    }
+
+    /// The block's properties sorted by name, since iterating over `properties` directly goes in an arbitrary order
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// let observer: BlockState = "observer[powered=false,facing=west]".parse().unwrap();
+    ///
+    /// assert_eq!(observer.properties_sorted(), vec![("facing", "west"), ("powered", "false")]);
+    /// ```
+    pub fn properties_sorted(&self) -> Vec<(&str, &str)> {
+        let mut properties: Vec<_> = self
+            .properties
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+
+        properties.sort_unstable();
+
+        properties
+    }
+
+    /// Lowercase the names and values of properties that only ever have lowercase words as values, like `facing` or `waterlogged`, so mixed case input like `Facing=North` becomes `facing=north`
+    ///
+    /// Minecraft treats properties as case sensitive, so this isn't done automatically. Properties that aren't in the built in table of these properties are left alone, so valid data is never changed. The table covers directions, orientations, shapes, halves, boolean flags, and the sides of walls, fences, and redstone wire, but not properties with free-form or numeric values
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// let mut furnace: BlockState = "furnace[Facing=North,LIT=True]".parse().unwrap();
+    ///
+    /// furnace.normalize_properties();
+    ///
+    /// assert_eq!(furnace, "furnace[facing=north,lit=true]".parse::<BlockState>().unwrap());
+    /// ```
+    pub fn normalize_properties(&mut self) {
+        let properties = std::mem::take(&mut self.properties);
+
+        for (name, value) in properties {
+            let lowercase = name.to_lowercase();
+
+            if ENUM_PROPERTIES.contains(&lowercase.as_str()) {
+                self.properties.insert(lowercase, value.to_lowercase());
+            } else {
+                self.properties.insert(name, value);
+            }
+        }
+    }
+
+    /// Check if the block's id is a valid resource location, a namespace and a path made of lowercase letters, digits, and `_-.`, with the path also allowing `/`
+    pub(crate) fn has_valid_id(&self) -> bool {
+        let (namespace, path) = match self.block.split_once(':') {
+            Some(v) => v,
+            None => return false,
+        };
+
+        let valid_char =
+            |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-.".contains(c);
+
+        !namespace.is_empty()
+            && !path.is_empty()
+            && namespace.chars().all(valid_char)
+            && path.chars().all(|c| valid_char(c) || c == '/')
+    }
+
     pub(crate) fn new_from_nbt(data: &NbtCompound) -> Result<BlockState, BlockStateParseError> {
         let properties_nbt = match data.get::<_, &NbtTag>("Properties").ok() {
             Some(v) => v.clone(),
@@ -93,7 +319,174 @@ impl BlockState {
         })
     }
 
-    fn prefix_block_name(name: &str) -> String {
+    /// The block state as it would look reflected across a plane perpendicular to the axis given
+    ///
+    /// These properties are changed, and every other property is kept as it is:
+    ///
+    /// - `facing` is flipped along the axis
+    /// - `half` and slab `type` swap `top` and `bottom`, and `half` swaps `upper` and `lower`, when mirroring along y
+    /// - `shape` swaps left and right for stairs, and flips the directions of rail shapes, when mirroring along x or z
+    /// - `hinge` and chest `type` swap `left` and `right` when mirroring along x or z
+    /// - `rotation`, used by signs, banners, and heads, is flipped when mirroring along x or z
+    ///
+    /// ```
+    /// # use litematic_editor::{Axis, BlockState};
+    /// let stairs: BlockState = "oak_stairs[facing=east,half=bottom,shape=inner_left]".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     stairs.mirrored(Axis::X),
+    ///     "oak_stairs[facing=west,half=bottom,shape=inner_right]".parse::<BlockState>().unwrap()
+    /// );
+    /// assert_eq!(
+    ///     stairs.mirrored(Axis::Y),
+    ///     "oak_stairs[facing=east,half=top,shape=inner_left]".parse::<BlockState>().unwrap()
+    /// );
+    /// ```
+    pub fn mirrored(&self, axis: Axis) -> BlockState {
+        let mut mirrored = self.clone();
+
+        const STAIR_SHAPES: &[(&str, &str)] =
+            &[("inner_left", "inner_right"), ("outer_left", "outer_right")];
+
+        let swaps: &[(&str, &[(&str, &str)])] = match axis {
+            Axis::X => &[
+                ("facing", &[("east", "west")]),
+                ("shape", STAIR_SHAPES),
+                (
+                    "shape",
+                    &[
+                        ("ascending_east", "ascending_west"),
+                        ("north_east", "north_west"),
+                        ("south_east", "south_west"),
+                    ],
+                ),
+                ("hinge", &[("left", "right")]),
+                ("type", &[("left", "right")]),
+            ],
+            Axis::Y => &[
+                ("facing", &[("up", "down")]),
+                ("half", &[("top", "bottom"), ("upper", "lower")]),
+                ("type", &[("top", "bottom")]),
+            ],
+            Axis::Z => &[
+                ("facing", &[("north", "south")]),
+                ("shape", STAIR_SHAPES),
+                (
+                    "shape",
+                    &[
+                        ("ascending_north", "ascending_south"),
+                        ("north_east", "south_east"),
+                        ("north_west", "south_west"),
+                    ],
+                ),
+                ("hinge", &[("left", "right")]),
+                ("type", &[("left", "right")]),
+            ],
+        };
+
+        for (property, pairs) in swaps {
+            if let Some(value) = mirrored.properties.get_mut(*property) {
+                if let Some(swapped) = BlockState::swap_value(value, pairs) {
+                    *value = swapped.to_string();
+                }
+            }
+        }
+
+        // Rotations count sixteenths of a turn clockwise from south, so 4 is west, 8 is north, and 12 is east
+        if let Some(rotation) = mirrored.properties.get_mut("rotation") {
+            if let Ok(value) = rotation.parse::<u8>() {
+                match axis {
+                    Axis::X => *rotation = ((16 - value % 16) % 16).to_string(),
+                    Axis::Z => *rotation = ((24 - value % 16) % 16).to_string(),
+                    Axis::Y => {}
+                }
+            }
+        }
+
+        mirrored
+    }
+
+    /// The block state as it would look rotated clockwise around the y axis by the amount of quarter turns given, as seen from above
+    ///
+    /// Negative amounts rotate counterclockwise. These properties are changed, and every other property is kept as it is:
+    ///
+    /// - Horizontal `facing` directions are rotated
+    /// - `axis` swaps `x` and `z` for odd amounts of quarter turns
+    /// - `shape` is rotated for rails, stair shapes don't change since they're relative to `facing`
+    /// - `rotation`, used by signs, banners, and heads, is rotated
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// let rail: BlockState = "rail[shape=north_east]".parse().unwrap();
+    ///
+    /// assert_eq!(rail.rotated(1), "rail[shape=south_east]".parse::<BlockState>().unwrap());
+    /// assert_eq!(rail.rotated(-1), "rail[shape=north_west]".parse::<BlockState>().unwrap());
+    /// assert_eq!(rail.rotated(4), rail);
+    /// ```
+    pub fn rotated(&self, quarter_turns: i32) -> BlockState {
+        let mut rotated = self.clone();
+        let turns = quarter_turns.rem_euclid(4) as usize;
+
+        const CYCLES: &[(&str, [&str; 4])] = &[
+            ("facing", ["north", "east", "south", "west"]),
+            (
+                "shape",
+                [
+                    "ascending_north",
+                    "ascending_east",
+                    "ascending_south",
+                    "ascending_west",
+                ],
+            ),
+            (
+                "shape",
+                ["north_east", "south_east", "south_west", "north_west"],
+            ),
+            (
+                "shape",
+                ["north_south", "east_west", "north_south", "east_west"],
+            ),
+            ("axis", ["x", "z", "x", "z"]),
+        ];
+
+        for (property, cycle) in CYCLES {
+            if let Some(value) = rotated.properties.get_mut(*property) {
+                if let Some(index) = cycle.iter().position(|v| v == value) {
+                    *value = cycle[(index + turns) % 4].to_string();
+                }
+            }
+        }
+
+        if let Some(rotation) = rotated.properties.get_mut("rotation") {
+            if let Ok(value) = rotation.parse::<usize>() {
+                *rotation = ((value + turns * 4) % 16).to_string();
+            }
+        }
+
+        rotated
+    }
+
+    /// Get the value paired with the value given, if it's in one of the pairs
+    fn swap_value(value: &str, pairs: &[(&'static str, &'static str)]) -> Option<&'static str> {
+        pairs.iter().find_map(|(a, b)| {
+            if value == *a {
+                Some(*b)
+            } else if value == *b {
+                Some(*a)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Orders block states by name and then by their sorted properties, used to keep palettes deterministic
+    pub(crate) fn palette_cmp(&self, other: &BlockState) -> Ordering {
+        self.block
+            .cmp(&other.block)
+            .then_with(|| self.properties_sorted().cmp(&other.properties_sorted()))
+    }
+
+    pub(crate) fn prefix_block_name(name: &str) -> String {
         if !name.contains(":") {
             return ("minecraft:".to_string() + &name).to_lowercase();
         }
@@ -102,9 +495,93 @@ impl BlockState {
     }
 }
 
+impl FromStr for BlockState {
+    type Err = BlockStateParseError;
+
+    /// Parse a block state written the way minecraft commands write them
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut properties = HashMap::new();
+    /// properties.insert("facing".to_string(), "west".to_string());
+    ///
+    /// assert_eq!("observer[facing=west]".parse::<BlockState>().unwrap(), BlockState::new("observer", Some(properties)));
+    /// assert_eq!("minecraft:stone".parse::<BlockState>().unwrap(), BlockState::new("stone", None));
+    /// assert!("observer[facing=west".parse::<BlockState>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || BlockStateParseError::MalformedString(s.to_string());
+
+        let (block, properties_str) = match s.find('[') {
+            Some(index) => {
+                if !s.ends_with(']') {
+                    return Err(malformed());
+                }
+
+                (&s[..index], Some(&s[index + 1..s.len() - 1]))
+            }
+            None => (s, None),
+        };
+
+        if block.is_empty() || block.contains(']') {
+            return Err(malformed());
+        }
+
+        let mut properties = HashMap::new();
+
+        if let Some(properties_str) = properties_str.filter(|v| !v.is_empty()) {
+            for property in properties_str.split(',') {
+                let mut split = property.splitn(2, '=');
+
+                match (split.next(), split.next()) {
+                    (Some(name), Some(value)) if !name.is_empty() => {
+                        properties.insert(name.trim().to_string(), value.trim().to_string());
+                    }
+                    _ => return Err(malformed()),
+                }
+            }
+        }
+
+        Ok(BlockState::new(block, Some(properties)))
+    }
+}
+
+impl fmt::Display for BlockState {
+    /// Write a block state the way minecraft commands write them, with the properties sorted by name
+    ///
+    /// ```
+    /// # use litematic_editor::BlockState;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut properties = HashMap::new();
+    /// properties.insert("powered".to_string(), "false".to_string());
+    /// properties.insert("facing".to_string(), "west".to_string());
+    ///
+    /// assert_eq!(BlockState::new("observer", Some(properties)).to_string(), "minecraft:observer[facing=west,powered=false]");
+    /// assert_eq!(BlockState::new("stone", None).to_string(), "minecraft:stone");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.block)?;
+
+        if !self.properties.is_empty() {
+            let properties: Vec<_> = self
+                .properties_sorted()
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+
+            write!(f, "[{}]", properties.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<T: AsRef<str> + ?Sized> PartialEq<T> for BlockState {
     fn eq(&self, other: &T) -> bool {
-        self.block == BlockState::prefix_block_name(other.as_ref())
+        self.matches_name(other.as_ref())
     }
 }
 
@@ -115,11 +592,12 @@ impl<T: AsRef<str>> From<T> for BlockState {
 }
 
 impl Into<NbtTag> for &BlockState {
+    /// Convert a block state to the compound used in palettes, inserting its properties sorted by name so that equal block states are written the same way
     fn into(self) -> NbtTag {
         let mut compound = NbtCompound::new();
         let mut properties = NbtCompound::new();
 
-        for (name, value) in self.properties.iter() {
+        for (name, value) in self.properties_sorted() {
             properties.insert(name, value);
         }
 
@@ -137,7 +615,8 @@ impl Hash for BlockState {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.block.hash(state);
 
-        for (key, value) in self.properties.iter() {
+        // Sorted so that equal block states hash the same regardless of the order of their properties
+        for (key, value) in self.properties_sorted() {
             key.hash(state);
             value.hash(state);
         }
@@ -146,11 +625,11 @@ impl Hash for BlockState {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
 
-    use quartz_nbt::NbtCompound;
+    use quartz_nbt::{NbtCompound, NbtTag};
 
-    use crate::BlockState;
+    use crate::{Axis, BlockState, BlockStateParseError};
 
     #[test]
     fn test_prefix_block_name() {
@@ -159,6 +638,274 @@ mod tests {
         assert_eq!(BlockState::prefix_block_name("CoOlMoD:aIr"), "coolmod:air");
     }
 
+    #[test]
+    fn test_has_valid_id() {
+        assert!(BlockState::new("stone", None).has_valid_id());
+        assert!(BlockState::new("cool_mod:blocks/fancy.block-2", None).has_valid_id());
+        assert!(!BlockState::new("stone block", None).has_valid_id());
+        assert!(!BlockState::new("a:b:c", None).has_valid_id());
+        assert!(!BlockState::new(":stone", None).has_valid_id());
+        assert!(!BlockState::new("minecraft:", None).has_valid_id());
+        assert!(!BlockState::new("cool/mod:stone", None).has_valid_id());
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let mut properties = HashMap::new();
+        properties.insert("axis".to_string(), "x".to_string());
+        properties.insert("waterlogged".to_string(), "false".to_string());
+
+        assert_eq!(
+            BlockState::from_parts("Basalt", &[("axis", "x"), ("waterlogged", "false")]),
+            BlockState::new("minecraft:basalt", Some(properties))
+        );
+        assert_eq!(
+            BlockState::from_parts("stone", &[]),
+            BlockState::new("stone", None)
+        );
+        assert_eq!(
+            BlockState::from_parts("basalt", &[("axis", "x"), ("axis", "z")]),
+            BlockState::new("basalt", None).with_property("axis", "z")
+        );
+    }
+
+    #[test]
+    fn test_air() {
+        assert_eq!(BlockState::air(), &BlockState::new("air", None));
+        assert_eq!(BlockState::air(), "minecraft:air");
+        assert!(std::ptr::eq(BlockState::air(), BlockState::air()));
+        assert_ne!(
+            BlockState::air(),
+            &BlockState::new("air", None).with_property("bruh", "true")
+        );
+    }
+
+    #[test]
+    fn test_normalize_properties() {
+        let mut observer = BlockState::from_parts(
+            "observer",
+            &[
+                ("Facing", "North"),
+                ("powered", "FALSE"),
+                ("CustomName", "Bob"),
+            ],
+        );
+
+        observer.normalize_properties();
+
+        assert_eq!(
+            observer,
+            BlockState::from_parts(
+                "observer",
+                &[
+                    ("facing", "north"),
+                    ("powered", "false"),
+                    ("CustomName", "Bob")
+                ]
+            )
+        );
+
+        let normalized = observer.clone();
+        observer.normalize_properties();
+
+        assert_eq!(observer, normalized);
+    }
+
+    #[test]
+    fn test_try_new() {
+        for id in [
+            "stone",
+            "minecraft:stone",
+            "STONE",
+            "cool_mod:blocks/fancy.block-2",
+            "a.b-c_d:e",
+        ] {
+            assert_eq!(
+                BlockState::try_new(id, None).unwrap(),
+                BlockState::new(id, None)
+            );
+        }
+
+        let mut properties = HashMap::new();
+        properties.insert("axis".to_string(), "x".to_string());
+
+        assert_eq!(
+            BlockState::try_new("basalt", Some(properties)).unwrap(),
+            BlockState::new("basalt", None).with_property("axis", "x")
+        );
+
+        for id in [
+            "minecraft::stone",
+            "stone bricks",
+            ":stone",
+            "minecraft:",
+            "cool/mod:stone",
+            "stone!",
+            "",
+        ] {
+            assert!(matches!(
+                BlockState::try_new(id, None),
+                Err(BlockStateParseError::InvalidId(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_mirrored() {
+        let stairs = BlockState::from_parts(
+            "oak_stairs",
+            &[
+                ("facing", "east"),
+                ("half", "bottom"),
+                ("shape", "outer_right"),
+            ],
+        );
+
+        assert_eq!(
+            stairs.mirrored(Axis::X),
+            BlockState::from_parts(
+                "oak_stairs",
+                &[
+                    ("facing", "west"),
+                    ("half", "bottom"),
+                    ("shape", "outer_left")
+                ]
+            )
+        );
+        assert_eq!(
+            stairs.mirrored(Axis::Z),
+            BlockState::from_parts(
+                "oak_stairs",
+                &[
+                    ("facing", "east"),
+                    ("half", "bottom"),
+                    ("shape", "outer_left")
+                ]
+            )
+        );
+        assert_eq!(stairs.mirrored(Axis::X).mirrored(Axis::X), stairs);
+
+        let slab = BlockState::from_parts("stone_slab", &[("type", "top")]);
+        let double = BlockState::from_parts("stone_slab", &[("type", "double")]);
+
+        assert_eq!(slab.mirrored(Axis::Y).properties["type"], "bottom");
+        assert_eq!(slab.mirrored(Axis::X), slab);
+        assert_eq!(double.mirrored(Axis::Y), double);
+
+        let rail = BlockState::from_parts("rail", &[("shape", "ascending_east")]);
+
+        assert_eq!(rail.mirrored(Axis::X).properties["shape"], "ascending_west");
+        assert_eq!(rail.mirrored(Axis::Z), rail);
+
+        let sign = BlockState::from_parts("oak_sign", &[("rotation", "4")]);
+
+        assert_eq!(sign.mirrored(Axis::X).properties["rotation"], "12");
+        assert_eq!(sign.mirrored(Axis::Z).properties["rotation"], "4");
+        assert_eq!(
+            sign.with_property("rotation", "0")
+                .mirrored(Axis::Z)
+                .properties["rotation"],
+            "8"
+        );
+
+        let chest = BlockState::from_parts("chest", &[("facing", "north"), ("type", "left")]);
+
+        assert_eq!(
+            chest.mirrored(Axis::X),
+            BlockState::from_parts("chest", &[("facing", "north"), ("type", "right")])
+        );
+    }
+
+    #[test]
+    fn test_rotated() {
+        let stairs = BlockState::from_parts(
+            "oak_stairs",
+            &[
+                ("facing", "north"),
+                ("half", "top"),
+                ("shape", "inner_left"),
+            ],
+        );
+
+        assert_eq!(
+            stairs.rotated(1),
+            BlockState::from_parts(
+                "oak_stairs",
+                &[("facing", "east"), ("half", "top"), ("shape", "inner_left")]
+            )
+        );
+        assert_eq!(stairs.rotated(2).properties["facing"], "south");
+        assert_eq!(stairs.rotated(-1), stairs.rotated(3));
+
+        let log = BlockState::from_parts("oak_log", &[("axis", "x")]);
+
+        assert_eq!(log.rotated(1).properties["axis"], "z");
+        assert_eq!(log.rotated(2), log);
+        assert_eq!(
+            log.with_property("axis", "y").rotated(1).properties["axis"],
+            "y"
+        );
+
+        let rail = BlockState::from_parts("rail", &[("shape", "north_south")]);
+
+        assert_eq!(rail.rotated(1).properties["shape"], "east_west");
+        assert_eq!(
+            rail.with_property("shape", "ascending_west")
+                .rotated(1)
+                .properties["shape"],
+            "ascending_north"
+        );
+
+        let sign = BlockState::from_parts("oak_sign", &[("rotation", "14")]);
+
+        assert_eq!(sign.rotated(1).properties["rotation"], "2");
+        assert_eq!(
+            BlockState::from_parts("observer", &[("facing", "up")]).rotated(1),
+            BlockState::from_parts("observer", &[("facing", "up")])
+        );
+    }
+
+    #[test]
+    fn test_hash() {
+        let mut set = HashSet::new();
+
+        for _ in 0..16 {
+            let mut properties = HashMap::new();
+
+            properties.insert("facing".to_string(), "west".to_string());
+            properties.insert("powered".to_string(), "false".to_string());
+            properties.insert("waterlogged".to_string(), "false".to_string());
+
+            set.insert(BlockState::new("observer", Some(properties)));
+        }
+
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let mut properties = HashMap::new();
+
+        properties.insert("facing".to_string(), "west".to_string());
+        properties.insert("powered".to_string(), "false".to_string());
+
+        assert_eq!(
+            "minecraft:observer[facing=west,powered=false]"
+                .parse::<BlockState>()
+                .unwrap(),
+            BlockState::new("observer", Some(properties))
+        );
+        assert_eq!(
+            "stone[]".parse::<BlockState>().unwrap(),
+            BlockState::new("stone", None)
+        );
+
+        assert!("".parse::<BlockState>().is_err());
+        assert!("stone[facing]".parse::<BlockState>().is_err());
+        assert!("stone[=west]".parse::<BlockState>().is_err());
+        assert!("stone]".parse::<BlockState>().is_err());
+    }
+
     #[test]
     fn test_new_from_nbt() {
         let mut compound = NbtCompound::new();
@@ -191,4 +938,73 @@ mod tests {
             BlockState::new("observer", Some(properties_map))
         )
     }
+
+    #[test]
+    fn test_into_nbt_tag() {
+        let names = [
+            "waterlogged",
+            "facing",
+            "powered",
+            "delay",
+            "locked",
+            "mode",
+        ];
+
+        let mut forwards = BlockState::new("repeater", None);
+        let mut backwards = BlockState::new("repeater", None);
+
+        for name in names.iter() {
+            forwards = forwards.with_property(name, "1");
+        }
+
+        for name in names.iter().rev() {
+            backwards = backwards.with_property(name, "1");
+        }
+
+        let first: NbtTag = (&forwards).into();
+        let second: NbtTag = (&forwards).into();
+        let third: NbtTag = (&backwards).into();
+
+        assert_eq!(first, second);
+        assert_eq!(first, third);
+
+        // Compounds compare equal regardless of the order of their tags, so the order they get written in is checked directly
+        let keys = |tag: &NbtTag| -> Vec<String> {
+            if let NbtTag::Compound(compound) = tag {
+                compound
+                    .get::<_, &NbtCompound>("Properties")
+                    .unwrap()
+                    .inner()
+                    .keys()
+                    .cloned()
+                    .collect()
+            } else {
+                panic!("Block states should be converted to compounds");
+            }
+        };
+
+        let mut sorted_names: Vec<_> = names.iter().map(|v| v.to_string()).collect();
+        sorted_names.sort();
+
+        assert_eq!(keys(&first), sorted_names);
+        assert_eq!(keys(&third), sorted_names);
+
+        let compound = if let NbtTag::Compound(compound) = first {
+            compound
+        } else {
+            panic!("Block states should be converted to compounds");
+        };
+
+        assert_eq!(
+            compound.get::<_, &NbtCompound>("Properties").unwrap().len(),
+            names.len()
+        );
+        assert_eq!(BlockState::new_from_nbt(&compound).unwrap(), forwards);
+
+        let stone: NbtTag = (&BlockState::new("stone", None)).into();
+
+        if let NbtTag::Compound(stone) = stone {
+            assert!(stone.get::<_, &NbtCompound>("Properties").is_err());
+        }
+    }
 }
@@ -0,0 +1,119 @@
+use quartz_nbt::{NbtCompound, NbtList, NbtTag};
+
+use crate::FVector3;
+
+/// A struct that represents an entity in a region, like a mob or an item frame
+#[derive(Debug, PartialEq, Clone)]
+pub struct Entity {
+    /// The entity's id, for example `minecraft:item_frame`
+    pub id: String,
+    /// The entity's position, relative to the region it's in
+    pub position: FVector3,
+    /// The rest of the entity's NBT data, excluding the `id` and `Pos` tags
+    pub nbt: NbtCompound,
+}
+
+impl Entity {
+    /// Create a new entity
+    ///
+    /// ```
+    /// use litematic_editor::{Entity, Vector3};
+    ///
+    /// let entity = Entity::new("minecraft:pig", Vector3::new(0.5, 0.0, 0.5));
+    ///
+    /// assert_eq!(entity.id, "minecraft:pig");
+    /// ```
+    pub fn new(id: &str, position: FVector3) -> Entity {
+        Entity {
+            id: id.to_string(),
+            position,
+            nbt: NbtCompound::new(),
+        }
+    }
+
+    pub(crate) fn new_from_nbt(data: &NbtCompound) -> Option<Entity> {
+        let mut position = Vec::new();
+
+        for tag in data.get::<_, &NbtList>("Pos").ok()? {
+            if let NbtTag::Double(value) = tag {
+                position.push(*value as f32);
+            } else {
+                return None;
+            }
+        }
+
+        if position.len() != 3 {
+            return None;
+        }
+
+        let mut nbt = NbtCompound::new();
+
+        for (name, tag) in data.inner() {
+            if name != "id" && name != "Pos" {
+                nbt.insert(name, tag.clone());
+            }
+        }
+
+        Some(Entity {
+            id: data.get::<_, &String>("id").ok()?.clone(),
+            position: FVector3::new(position[0], position[1], position[2]),
+            nbt,
+        })
+    }
+}
+
+impl From<&Entity> for NbtTag {
+    fn from(entity: &Entity) -> NbtTag {
+        let mut compound = entity.nbt.clone();
+        let mut position = NbtList::new();
+
+        position.push(entity.position.x as f64);
+        position.push(entity.position.y as f64);
+        position.push(entity.position.z as f64);
+
+        compound.insert("id", entity.id.clone());
+        compound.insert("Pos", position);
+
+        NbtTag::Compound(compound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quartz_nbt::{NbtCompound, NbtList, NbtTag};
+
+    use crate::{Entity, Vector3};
+
+    #[test]
+    fn test_new_from_nbt() {
+        let mut compound = NbtCompound::new();
+        let mut position = NbtList::new();
+
+        position.push(1.5_f64);
+        position.push(2.0_f64);
+        position.push(-3.25_f64);
+
+        compound.insert("id", "minecraft:item_frame");
+        compound.insert("Pos", position);
+        compound.insert("Facing", 2_i8);
+
+        let entity = Entity::new_from_nbt(&compound).unwrap();
+
+        assert_eq!(entity.id, "minecraft:item_frame");
+        assert_eq!(entity.position, Vector3::new(1.5, 2.0, -3.25));
+        assert_eq!(entity.nbt.len(), 1);
+        assert_eq!(entity.nbt.get::<_, i8>("Facing").unwrap(), 2);
+
+        let tag: NbtTag = (&entity).into();
+
+        if let NbtTag::Compound(tag) = tag {
+            assert_eq!(tag, compound);
+        } else {
+            panic!("`into` conversion didn't produce a compound");
+        }
+
+        compound.insert("Pos", NbtList::new());
+
+        assert_eq!(Entity::new_from_nbt(&compound), None);
+    }
+}
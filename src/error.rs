@@ -1,6 +1,8 @@
 use quartz_nbt::{io::NbtIoError, NbtReprError, NbtStructureError};
 use thiserror::Error;
 
+use crate::Vector3;
+
 /// The error returned when attempting to parse a buffer as a schematic fails
 #[derive(Error, Debug)]
 pub enum LitematicParseError {
@@ -17,8 +19,14 @@ pub enum LitematicParseError {
     #[error("The tag {0} is the wrong type")]
     WrongTag(String),
     /// When there's an issue parsing one of the regions
-    #[error("There was an error parsing a region: {0}")]
-    RegionParseError(#[from] RegionParseError),
+    #[error("There was an error parsing the region '{region}': {error}")]
+    RegionParseError {
+        /// The name of the region that couldn't be parsed
+        region: String,
+        /// The error parsing the region
+        #[source]
+        error: RegionParseError,
+    },
     /// Any unknown error occured
     #[error("An unknown error occured")]
     Unknown,
@@ -36,6 +44,23 @@ pub enum RegionParseError {
     /// When a tag is the wrong type, contains the name of the incorrect tag
     #[error("The tag {0} is the wrong type")]
     WrongTag(String),
+    /// When the block data of a region is malformed or doesn't match the region's size and palette
+    #[error("The region's block data is malformed")]
+    MalformedBlockData,
+    /// When the region's size is zero along one of the axes, contains the size
+    #[error("The region has an invalid size of {0:?}")]
+    InvalidDimensions(Vector3<i32>),
+    /// When the region's palette doesn't have any entries, even though it should always contain air
+    #[error("The region's palette is empty")]
+    EmptyPalette,
+    /// When the block states array doesn't have the length required by the region's size and palette
+    #[error("The region has {found} longs of block states, but {expected} were expected")]
+    BlockStatesLengthMismatch {
+        /// The amount of longs required by the region's size and palette
+        expected: usize,
+        /// The amount of longs in the block states array
+        found: usize,
+    },
     /// When any unknown error occurs
     #[error("An unknown error occured")]
     Unknown,
@@ -50,11 +75,166 @@ pub enum BlockStateParseError {
     /// When a tag is the wrong type, contains the name of the incorrect tag
     #[error("The tag {0} is the wrong type")]
     WrongTag(String),
+    /// When a block state string like `minecraft:observer[facing=west]` is malformed, contains the string
+    #[error("The block state {0} is malformed")]
+    MalformedString(String),
+    /// When a block id isn't a valid resource location like `minecraft:stone`, contains the id
+    #[error("The block id {0} is invalid")]
+    InvalidId(String),
     /// When any unknown error occurs
     #[error("An unknown error occured")]
     Unknown,
 }
 
+/// The error returned when attempting to write a schematic fails
+#[derive(Error, Debug)]
+pub enum LitematicWriteError {
+    /// When the NBT data can't be written, like when the writer given fails
+    #[error("The NBT data couldn't be written: {0}")]
+    NBTWriteError(#[from] NbtIoError),
+    /// When there's an issue writing one of the regions
+    #[error("There was an error writing a region: {0}")]
+    RegionWriteError(#[from] RegionWriteError),
+    /// When the schematic is larger than 65535 blocks along an axis, so it can't be written as a `.schem` file, contains the size of the schematic
+    #[error("The schematic's size of {0:?} is too large for the .schem format")]
+    TooLargeForSchem(Vector3<i32>),
+}
+
+/// An error from reading, writing, or parsing a schematic, for code that does several of those and wants to use `?` with a single error type
+///
+/// ```
+/// # use litematic_editor::{LitematicError, Schematic};
+/// # use std::path::Path;
+/// fn touch(path: &Path) -> Result<(), LitematicError> {
+///     let data = std::fs::read(path)?;
+///     let mut schematic = Schematic::from_buffer(&mut data.as_slice())?;
+///
+///     schematic.description = "Touched".to_string();
+///
+///     std::fs::write(path, schematic.to_buffer()?)?;
+///
+///     Ok(())
+/// }
+///
+/// let path = std::env::temp_dir().join("litematic-editor-touch-example.litematic");
+/// std::fs::write(&path, Schematic::new(None, None, None, None).to_buffer().unwrap()).unwrap();
+///
+/// assert!(touch(&path).is_ok());
+/// assert!(matches!(touch(Path::new("test/path/to/nothing.litematic")), Err(LitematicError::Io(_))));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Error, Debug)]
+pub enum LitematicError {
+    /// When reading or writing a file or other IO fails
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    /// When parsing a schematic fails
+    #[error("{0}")]
+    Parse(#[from] LitematicParseError),
+    /// When writing a schematic fails
+    #[error("{0}")]
+    Write(#[from] LitematicWriteError),
+}
+
+/// The error returned when attempting to convert a region to NBT data fails
+#[derive(Error, Debug)]
+pub enum RegionWriteError {
+    /// When the generated block states array doesn't have the length required by the region's volume and palette
+    #[error("Generated {found} longs of block states, but {expected} were expected")]
+    BlockStatesLengthMismatch {
+        /// The amount of longs required by the region's volume and palette
+        expected: usize,
+        /// The amount of longs that were generated
+        found: usize,
+    },
+    /// When a block is outside of the volume the region is being saved with, so it has nowhere to go in the block states array
+    #[error("The block at {0:?} is outside of the volume being saved")]
+    BlockOutsideVolume(Vector3<i32>),
+    /// When the region has more distinct block states than can be indexed in the block states array, contains the size of the palette
+    #[error("The palette has {0} entries, which is too many to pack into the block states array")]
+    PaletteTooLarge(usize),
+}
+
+/// The error returned when a name can't be used for a region, by [Schematic::set_region()](crate::Schematic::set_region)
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RegionNameError {
+    /// When the name is empty, which litematica can't look the region up by
+    #[error("Region names can't be empty")]
+    Empty,
+}
+
+/// The error returned when a palette index mapping can't be applied to a region
+#[derive(Error, Debug)]
+pub enum PaletteRemapError {
+    /// When the mapping doesn't have exactly one entry per palette entry
+    #[error("The mapping has {found} entries, but the palette has {expected}")]
+    WrongLength {
+        /// The length of the region's palette
+        expected: usize,
+        /// The length of the mapping given
+        found: usize,
+    },
+    /// When the mapping contains an index that's outside of the palette, contains the index
+    #[error("The index {0} is outside of the palette")]
+    IndexOutOfRange(usize),
+}
+
+/// The error returned when attempting to parse minecraft commands as a region fails
+#[derive(Error, Debug)]
+pub enum CommandParseError {
+    /// When a line isn't a `/setblock` or `/fill` command, contains the line
+    #[error("The command {0} isn't a setblock or fill command")]
+    UnsupportedCommand(String),
+    /// When a command has the wrong arguments, contains the line
+    #[error("The command {0} is malformed")]
+    MalformedCommand(String),
+    /// When a command uses relative or local coordinates, contains the line
+    #[error("The command {0} uses relative coordinates, which aren't supported")]
+    RelativeCoordinates(String),
+    /// When parsing one of the block states fails
+    #[error("Error parsing one of the block states: {0}")]
+    BlockStateParseError(#[from] BlockStateParseError),
+}
+
+/// A problem with a schematic found by [Schematic::validate()](crate::Schematic::validate), which could make it get written incorrectly or fail to load in litematica
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// When the region count the schematic was read with doesn't match how many regions it has
+    #[error("The schematic says it has {stored} regions, but it has {actual}")]
+    RegionCountMismatch {
+        /// The region count the schematic was read with
+        stored: i32,
+        /// The amount of regions the schematic has
+        actual: usize,
+    },
+    /// When the volume taken up by a region holds more blocks than fit in an `i32`, so its block states can't be written
+    #[error(
+        "The region {region} has a size of {size:?}, which holds too many blocks to be written"
+    )]
+    VolumeTooLarge {
+        /// The name of the region
+        region: String,
+        /// The size of the region's volume
+        size: Vector3<i32>,
+    },
+    /// When a region uses more distinct block states than a palette can reasonably hold
+    #[error("The region {region} has {size} distinct block states")]
+    LargePalette {
+        /// The name of the region
+        region: String,
+        /// The amount of distinct block states in the region
+        size: usize,
+    },
+    /// When a block's id isn't a valid resource location like `minecraft:stone`
+    #[error("The region {region} has a block with the malformed id {id}")]
+    MalformedBlockId {
+        /// The name of the region
+        region: String,
+        /// The block's id
+        id: String,
+    },
+}
+
 impl From<NbtReprError> for LitematicParseError {
     fn from(data: NbtReprError) -> LitematicParseError {
         match data {
@@ -0,0 +1,438 @@
+use quartz_nbt::{
+    io::{self, Flavor, NbtIoError},
+    NbtCompound, NbtTag,
+};
+use std::{
+    collections::HashMap,
+    io::{ErrorKind, Read},
+};
+
+use crate::{LitematicParseError, Region, Schematic};
+
+/// The top level tags in a schematic's NBT data that get parsed, rather than kept in its `extra` tags
+const KNOWN_TAGS: &[&str] = &["Metadata", "MinecraftDataVersion", "Version", "Regions"];
+
+/// A schematic whose regions are only parsed when they're first accessed
+///
+/// The whole file still gets read and decompressed up front, but turning a region's NBT data into blocks is put off until [get_region()](LazySchematic::get_region) is called for it, which saves time for files with lots of large regions
+pub struct LazySchematic {
+    /// A schematic's author
+    pub author: String,
+    /// A schematic's description
+    pub description: String,
+    /// A schematic's name
+    pub name: String,
+    time_created: i64,
+    /// Last time a schematic was modified, in milliseconds since 1970
+    pub time_modified: i64,
+    data_version: i32,
+    region_count: Option<i32>,
+    preview_image_data: Option<Vec<i32>>,
+    extra: NbtCompound,
+    raw_regions: HashMap<String, NbtCompound>,
+    regions: HashMap<String, Region>,
+}
+
+impl LazySchematic {
+    /// Read a schematic's metadata from a buffer, without parsing any of its regions
+    ///
+    /// The data can be gzip compressed like litematica writes it, zlib compressed, or uncompressed, which is detected from its first few bytes
+    ///
+    /// ```
+    /// use litematic_editor::LazySchematic;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut schematic = LazySchematic::from_buffer(&mut File::open("test/path/to/schematic.litematic")?)?;
+    ///
+    /// let names: Vec<String> = schematic.region_names().map(|v| v.to_string()).collect();
+    ///
+    /// for name in names {
+    ///     let region = schematic.get_region(&name)?.unwrap();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_buffer(data: &mut impl Read) -> Result<LazySchematic, LitematicParseError> {
+        let mut header = [0_u8; 2];
+        let mut header_length = 0;
+
+        // Readers like pipes can give back fewer bytes than asked for, so keep reading until the whole header is in or the data runs out
+        while header_length < header.len() {
+            match data.read(&mut header[header_length..]) {
+                Ok(0) => break,
+                Ok(read) => header_length += read,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(NbtIoError::from(e).into()),
+            }
+        }
+
+        let header = &header[..header_length];
+
+        LazySchematic::from_buffer_with_flavor(
+            &mut header.chain(data),
+            LazySchematic::detect_flavor(header),
+        )
+    }
+
+    /// Work out how NBT data is compressed from its first bytes, which are the gzip or zlib header if it's compressed, or the id of the root compound tag if it isn't
+    fn detect_flavor(start: &[u8]) -> Flavor {
+        match start {
+            [0x1f, 0x8b, ..] => Flavor::GzCompressed,
+            [0x78, ..] => Flavor::ZlibCompressed,
+            _ => Flavor::Uncompressed,
+        }
+    }
+
+    /// Read a schematic's metadata from a buffer, decompressing it the way the flavor given says to
+    pub fn from_buffer_with_flavor(
+        data: &mut impl Read,
+        flavor: Flavor,
+    ) -> Result<LazySchematic, LitematicParseError> {
+        let parsed_data = io::read_nbt(data, flavor)?.0;
+
+        let version = parsed_data.get::<_, i32>("Version")?;
+
+        if version != 5 {
+            return Err(LitematicParseError::UnsupportedVersionNumber(version));
+        }
+
+        let metadata = parsed_data.get::<_, &NbtCompound>("Metadata")?;
+
+        // The author and description are left out by some tools, and the region count can be worked out from the regions
+        let get_optional_string =
+            |key: &str| metadata.get::<_, &String>(key).cloned().unwrap_or_default();
+
+        let preview_image_data = match metadata.get::<_, &NbtTag>("PreviewImageData").ok() {
+            Some(NbtTag::IntArray(data)) => Some(data.clone()),
+            Some(_) => {
+                return Err(LitematicParseError::WrongTag(
+                    "PreviewImageData".to_string(),
+                ))
+            }
+            None => None,
+        };
+
+        let mut extra = NbtCompound::new();
+
+        for (key, tag) in parsed_data.inner() {
+            if !KNOWN_TAGS.contains(&key.as_str()) {
+                extra.insert(key.clone(), tag.clone());
+            }
+        }
+
+        Ok(LazySchematic {
+            author: get_optional_string("Author"),
+            description: get_optional_string("Description"),
+            name: metadata.get::<_, &String>("Name")?.clone(),
+            time_created: metadata.get::<_, i64>("TimeCreated")?,
+            time_modified: metadata.get::<_, i64>("TimeModified")?,
+            data_version: parsed_data.get::<_, i32>("MinecraftDataVersion")?,
+            region_count: metadata.get::<_, i32>("RegionCount").ok(),
+            preview_image_data,
+            extra,
+            raw_regions: LazySchematic::find_regions(&parsed_data)?,
+            regions: HashMap::new(),
+        })
+    }
+
+    /// An iterator over the names of all the regions in the schematic, whether they've been parsed or not
+    pub fn region_names(&self) -> impl Iterator<Item = &str> {
+        self.raw_regions
+            .keys()
+            .chain(self.regions.keys())
+            .map(|v| v.as_str())
+    }
+
+    /// Check if the region with the given name has been parsed yet
+    pub fn is_parsed(&self, name: &str) -> bool {
+        self.regions.contains_key(name)
+    }
+
+    /// Get the region with the given name, parsing it if it hasn't been already
+    ///
+    /// Returns `Ok(None)` if there's no region with that name. The region's NBT data is only dropped once it's been parsed, so a region that fails to parse gives the same error each time it's accessed
+    pub fn get_region(&mut self, name: &str) -> Result<Option<&Region>, LitematicParseError> {
+        if !self.regions.contains_key(name) {
+            let raw_region = match self.raw_regions.get(name) {
+                Some(v) => v,
+                None => return Ok(None),
+            };
+
+            let region = Region::new_from_nbt(raw_region).map_err(|error| {
+                LitematicParseError::RegionParseError {
+                    region: name.to_string(),
+                    error,
+                }
+            })?;
+
+            self.raw_regions.remove(name);
+            self.regions.insert(name.to_string(), region);
+        }
+
+        Ok(self.regions.get(name))
+    }
+
+    /// Parse all the remaining regions and convert this into a regular [Schematic]
+    pub fn into_schematic(self) -> Result<Schematic, LitematicParseError> {
+        let mut regions = self.regions;
+
+        for (name, raw_region) in self.raw_regions {
+            let region = Region::new_from_nbt(&raw_region).map_err(|error| {
+                LitematicParseError::RegionParseError {
+                    region: name.clone(),
+                    error,
+                }
+            })?;
+
+            regions.insert(name, region);
+        }
+
+        Ok(Schematic {
+            author: self.author,
+            description: self.description,
+            name: self.name,
+            time_created: self.time_created,
+            time_modified: self.time_modified,
+            regions,
+            data_version: self.data_version,
+            region_count: self.region_count,
+            preview_image_data: self.preview_image_data,
+            extra: self.extra,
+        })
+    }
+
+    fn find_regions(
+        data: &NbtCompound,
+    ) -> Result<HashMap<String, NbtCompound>, LitematicParseError> {
+        let regions_nbt =
+            if let NbtTag::Compound(regions_nbt) = data.get::<_, &NbtTag>("Regions")?.clone() {
+                regions_nbt
+            } else {
+                return Err(LitematicParseError::WrongTag("Regions".to_string()));
+            };
+
+        let regions = regions_nbt.into_inner();
+
+        let mut regions_found = HashMap::new();
+
+        for region_unknown in regions {
+            if let NbtTag::Compound(region) = region_unknown.1 {
+                regions_found.insert(region_unknown.0, region);
+            } else {
+                return Err(LitematicParseError::WrongTag(region_unknown.0));
+            }
+        }
+
+        Ok(regions_found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quartz_nbt::{
+        io::{self, Flavor},
+        NbtCompound, NbtTag,
+    };
+    use std::io::Read;
+
+    use crate::{LazySchematic, LitematicParseError, Region, Schematic, Vector3};
+
+    /// A reader that gives back one byte at a time, like a slow pipe
+    struct OneByteAtATime<'a>(&'a [u8]);
+
+    impl Read for OneByteAtATime<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.0.len().min(buf.len()).min(1);
+
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+
+            Ok(len)
+        }
+    }
+
+    fn two_region_buffer(corrupt_second: bool) -> Vec<u8> {
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+        let mut region1 = Region::new();
+        region1.set_block(Vector3::new(1, 2, 3), "stone");
+
+        let mut region2 = Region::new();
+        region2.set_block(Vector3::new(0, 0, 0), "dirt");
+
+        schematic.regions.insert("region1".to_string(), region1);
+        schematic.regions.insert("region2".to_string(), region2);
+
+        let buffer = schematic.to_buffer().unwrap();
+
+        if !corrupt_second {
+            return buffer;
+        }
+
+        let mut nbt = io::read_nbt(&mut buffer.as_slice(), Flavor::GzCompressed)
+            .unwrap()
+            .0;
+
+        let mut regions = nbt.get::<_, &NbtCompound>("Regions").unwrap().clone();
+        let mut region2 = regions.get::<_, &NbtCompound>("region2").unwrap().clone();
+
+        region2.insert("BlockStatePalette", 5);
+        regions.insert("region2", region2);
+        nbt.insert("Regions", regions);
+
+        let mut out = Vec::new();
+
+        io::write_nbt(&mut out, None, &nbt, Flavor::GzCompressed).unwrap();
+
+        out
+    }
+
+    #[test]
+    fn test_get_region() {
+        let mut schematic =
+            LazySchematic::from_buffer(&mut two_region_buffer(false).as_slice()).unwrap();
+
+        assert_eq!(schematic.name, "bruh");
+        assert_eq!(schematic.region_names().count(), 2);
+        assert!(!schematic.is_parsed("region1"));
+
+        let region = schematic.get_region("region1").unwrap().unwrap();
+
+        assert_eq!(region.blocks()[&Vector3::new(1, 2, 3)], "stone");
+        assert!(schematic.is_parsed("region1"));
+        assert!(!schematic.is_parsed("region2"));
+        assert_eq!(schematic.region_names().count(), 2);
+
+        assert!(schematic.get_region("region1").unwrap().is_some());
+        assert!(schematic.get_region("region3").unwrap().is_none());
+
+        let schematic = schematic.into_schematic().unwrap();
+
+        assert_eq!(schematic.regions.len(), 2);
+        assert_eq!(
+            schematic.regions["region2"].blocks()[&Vector3::new(0, 0, 0)],
+            "dirt"
+        );
+    }
+
+    #[test]
+    fn test_missing_optional_metadata() {
+        let buffer = two_region_buffer(false);
+
+        let mut nbt = io::read_nbt(&mut buffer.as_slice(), Flavor::GzCompressed)
+            .unwrap()
+            .0;
+
+        let full_metadata = nbt.get::<_, &NbtCompound>("Metadata").unwrap().clone();
+        let mut metadata = NbtCompound::new();
+
+        for key in ["Name", "TimeCreated", "TimeModified"] {
+            metadata.insert(key, full_metadata.get::<_, &NbtTag>(key).unwrap().clone());
+        }
+
+        nbt.insert("Metadata", metadata);
+
+        let mut out = Vec::new();
+        io::write_nbt(&mut out, None, &nbt, Flavor::GzCompressed).unwrap();
+
+        let schematic = LazySchematic::from_buffer(&mut out.as_slice()).unwrap();
+
+        assert_eq!(schematic.name, "bruh");
+        assert_eq!(schematic.author, "");
+        assert_eq!(schematic.description, "");
+
+        let schematic = schematic.into_schematic().unwrap();
+        let written = schematic.to_buffer().unwrap();
+        let nbt = io::read_nbt(&mut written.as_slice(), Flavor::GzCompressed)
+            .unwrap()
+            .0;
+
+        assert_eq!(
+            nbt.get::<_, &NbtCompound>("Metadata")
+                .unwrap()
+                .get::<_, i32>("RegionCount")
+                .unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_detect_flavor() {
+        let schematic = Schematic::from_buffer(&mut two_region_buffer(false).as_slice()).unwrap();
+
+        for flavor in [
+            Flavor::Uncompressed,
+            Flavor::ZlibCompressed,
+            Flavor::GzCompressed,
+        ] {
+            let buffer = schematic.to_buffer_with_flavor(flavor).unwrap();
+
+            let mut read = LazySchematic::from_buffer(&mut buffer.as_slice()).unwrap();
+
+            assert_eq!(read.name, "bruh");
+            assert_eq!(
+                read.get_region("region1").unwrap().unwrap().blocks()[&Vector3::new(1, 2, 3)],
+                "stone"
+            );
+
+            let mut read =
+                LazySchematic::from_buffer(&mut OneByteAtATime(buffer.as_slice())).unwrap();
+
+            assert_eq!(read.name, "bruh");
+            assert!(read.get_region("region1").unwrap().is_some());
+        }
+
+        assert!(matches!(
+            LazySchematic::detect_flavor(&[0x0a, 0, 0]),
+            Flavor::Uncompressed
+        ));
+        assert!(matches!(
+            LazySchematic::detect_flavor(&[0x1f, 0x8b, 8]),
+            Flavor::GzCompressed
+        ));
+
+        assert!(LazySchematic::from_buffer(&mut [].as_slice()).is_err());
+        assert!(LazySchematic::from_buffer(&mut [0x1f, 0x8b, 0, 0].as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_regions_parsed_only_on_access() {
+        let mut schematic =
+            LazySchematic::from_buffer(&mut two_region_buffer(true).as_slice()).unwrap();
+
+        assert_eq!(schematic.name, "bruh");
+        assert!(schematic.get_region("region1").unwrap().is_some());
+        assert!(schematic.get_region("region2").is_err());
+        assert!(schematic.into_schematic().is_err());
+
+        assert!(Schematic::from_buffer(&mut two_region_buffer(true).as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_region_parse_error_names_region() {
+        let err = match Schematic::from_buffer(&mut two_region_buffer(true).as_slice()) {
+            Ok(_) => panic!("The corrupt region should fail to parse"),
+            Err(err) => err,
+        };
+
+        assert!(matches!(
+            &err,
+            LitematicParseError::RegionParseError { region, .. } if region == "region2"
+        ));
+        assert!(err.to_string().contains("'region2'"));
+        assert!(std::error::Error::source(&err).is_some());
+
+        let mut lazy = LazySchematic::from_buffer(&mut two_region_buffer(true).as_slice()).unwrap();
+
+        assert!(lazy.get_region("region1").unwrap().is_some());
+
+        for _ in 0..2 {
+            assert!(matches!(
+                lazy.get_region("region2"),
+                Err(LitematicParseError::RegionParseError { region, .. }) if region == "region2"
+            ));
+            assert!(lazy.region_names().any(|name| name == "region2"));
+        }
+    }
+}
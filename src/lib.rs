@@ -30,7 +30,7 @@
 //!     }
 //!
 //!     // Convert the modified schematic into a u8 vector
-//!     let modified_data = schematic.to_buffer();
+//!     let modified_data = schematic.to_buffer().unwrap();
 //!
 //!     // Write the new schematic to a new litematic file
 //!     fs::write("test/path/to/schematic-modified.litematic", modified_data).unwrap();
@@ -41,16 +41,26 @@
 #![deny(rustdoc::private_intra_doc_links)]
 #![warn(missing_docs)]
 
+mod axis;
 mod block_state;
+mod entity;
 mod error;
+mod lazy_schematic;
 mod region;
 mod schematic;
 mod vector;
 mod volume;
 
+pub use axis::Axis;
 pub use block_state::BlockState;
-pub use error::{BlockStateParseError, LitematicParseError, RegionParseError};
-pub use region::Region;
-pub use schematic::Schematic;
+pub use entity::Entity;
+pub use error::{
+    BlockStateParseError, CommandParseError, LitematicError, LitematicParseError,
+    LitematicWriteError, PaletteRemapError, RegionNameError, RegionParseError, RegionWriteError,
+    ValidationWarning,
+};
+pub use lazy_schematic::LazySchematic;
+pub use region::{Blocks, BlocksIter, MergeStrategy, Region, RegionDiff, RegionIter};
+pub use schematic::{SaveOptions, Schematic};
 pub use vector::{FVector3, IVector3, UVector3, Vector3};
 pub use volume::Volume;
@@ -0,0 +1,119 @@
+use std::{
+    collections::{hash_map, HashMap},
+    ops::Index,
+};
+
+use crate::{BlockState, Vector3};
+
+/// A read only view of the blocks in a region, excluding air blocks, returned by [Region::blocks()](crate::Region::blocks)
+///
+/// Works like a `HashMap<Vector3<i32>, BlockState>`, but regions only store an index into their palette for each block, so the block states are looked up when they're accessed
+#[derive(Clone, Copy)]
+pub struct Blocks<'a> {
+    pub(super) blocks: &'a HashMap<Vector3<i32>, usize>,
+    pub(super) palette: &'a [BlockState],
+}
+
+impl<'a> Blocks<'a> {
+    /// The amount of blocks in the region
+    pub fn len(self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Check if there aren't any blocks in the region
+    pub fn is_empty(self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Get the block at the given position, or `None` if there's air there
+    pub fn get(self, pos: &Vector3<i32>) -> Option<&'a BlockState> {
+        self.blocks.get(pos).map(|index| &self.palette[*index])
+    }
+
+    /// Check if there's a block at the given position
+    pub fn contains_key(self, pos: &Vector3<i32>) -> bool {
+        self.blocks.contains_key(pos)
+    }
+
+    /// An iterator over the positions of all the blocks, in an arbitrary order
+    pub fn keys(self) -> hash_map::Keys<'a, Vector3<i32>, usize> {
+        self.blocks.keys()
+    }
+
+    /// An iterator over all the blocks, in an arbitrary order
+    pub fn values(self) -> impl Iterator<Item = &'a BlockState> {
+        self.iter().map(|(_, block)| block)
+    }
+
+    /// An iterator over the positions of the blocks and the blocks, in an arbitrary order
+    pub fn iter(self) -> BlocksIter<'a> {
+        BlocksIter {
+            blocks: self.blocks.iter(),
+            palette: self.palette,
+        }
+    }
+}
+
+impl<'a> Index<&Vector3<i32>> for Blocks<'a> {
+    type Output = BlockState;
+
+    /// Get the block at the given position
+    ///
+    /// Panics if there's air at the position
+    fn index(&self, pos: &Vector3<i32>) -> &BlockState {
+        &self.palette[self.blocks[pos]]
+    }
+}
+
+impl<'a> IntoIterator for Blocks<'a> {
+    type Item = (&'a Vector3<i32>, &'a BlockState);
+    type IntoIter = BlocksIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the blocks in a region, returned by [Blocks::iter()]
+pub struct BlocksIter<'a> {
+    blocks: hash_map::Iter<'a, Vector3<i32>, usize>,
+    palette: &'a [BlockState],
+}
+
+impl<'a> Iterator for BlocksIter<'a> {
+    type Item = (&'a Vector3<i32>, &'a BlockState);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let palette = self.palette;
+
+        self.blocks
+            .next()
+            .map(|(pos, index)| (pos, &palette[*index]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.blocks.size_hint()
+    }
+}
+
+/// An iterator over the blocks in a region with their positions in the schematic's coordinates, returned by [Region::iter()](crate::Region::iter)
+pub struct RegionIter<'a> {
+    pub(super) blocks: BlocksIter<'a>,
+    pub(super) origin: Vector3<i32>,
+}
+
+impl<'a> Iterator for RegionIter<'a> {
+    type Item = (Vector3<i32>, &'a BlockState);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let origin = self.origin;
+
+        self.blocks
+            .next()
+            .map(|(pos, block)| (*pos + origin, block))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.blocks.size_hint()
+    }
+}
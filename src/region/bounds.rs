@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use crate::{volume::Volume, Vector3};
+
+/// Keeps track of the bounding box of a set of positions as positions are added and removed
+///
+/// Stores how many positions there are at each coordinate along each axis, so the lowest and highest coordinates can be found without going through every position
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct BlockBounds {
+    x: BTreeMap<i32, usize>,
+    y: BTreeMap<i32, usize>,
+    z: BTreeMap<i32, usize>,
+}
+
+impl BlockBounds {
+    pub(super) fn from_positions<'a>(
+        positions: impl Iterator<Item = &'a Vector3<i32>>,
+    ) -> BlockBounds {
+        let mut bounds = BlockBounds::default();
+
+        for pos in positions {
+            bounds.add(*pos);
+        }
+
+        bounds
+    }
+
+    /// Add a position, which must not already be counted
+    pub(super) fn add(&mut self, pos: Vector3<i32>) {
+        *self.x.entry(pos.x).or_insert(0) += 1;
+        *self.y.entry(pos.y).or_insert(0) += 1;
+        *self.z.entry(pos.z).or_insert(0) += 1;
+    }
+
+    /// Remove a position, which must have been added before
+    pub(super) fn remove(&mut self, pos: Vector3<i32>) {
+        BlockBounds::remove_coord(&mut self.x, pos.x);
+        BlockBounds::remove_coord(&mut self.y, pos.y);
+        BlockBounds::remove_coord(&mut self.z, pos.z);
+    }
+
+    /// The smallest volume containing every position, with a positive size, or `None` if there aren't any positions
+    pub(super) fn volume(&self) -> Option<Volume> {
+        let min = Vector3::new(
+            *self.x.keys().next()?,
+            *self.y.keys().next()?,
+            *self.z.keys().next()?,
+        );
+        let max = Vector3::new(
+            *self.x.keys().next_back()?,
+            *self.y.keys().next_back()?,
+            *self.z.keys().next_back()?,
+        );
+
+        Some(Volume::new(min, max - min + Vector3::new(1, 1, 1)))
+    }
+
+    fn remove_coord(counts: &mut BTreeMap<i32, usize>, coord: i32) {
+        if let Some(count) = counts.get_mut(&coord) {
+            *count -= 1;
+
+            if *count == 0 {
+                counts.remove(&coord);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Vector3, Volume};
+
+    use super::BlockBounds;
+
+    #[test]
+    fn test_add_and_remove() {
+        let mut bounds = BlockBounds::default();
+
+        assert_eq!(bounds.volume(), None);
+
+        bounds.add(Vector3::new(0, 0, 0));
+        bounds.add(Vector3::new(3, -2, 1));
+        bounds.add(Vector3::new(3, 1, 1));
+
+        assert_eq!(
+            bounds.volume(),
+            Some(Volume::new(Vector3::new(0, -2, 0), Vector3::new(4, 4, 2)))
+        );
+
+        bounds.remove(Vector3::new(3, -2, 1));
+
+        assert_eq!(
+            bounds.volume(),
+            Some(Volume::new(Vector3::new(0, 0, 0), Vector3::new(4, 2, 2)))
+        );
+
+        bounds.remove(Vector3::new(0, 0, 0));
+        bounds.remove(Vector3::new(3, 1, 1));
+
+        assert_eq!(bounds.volume(), None);
+    }
+}
@@ -1,4 +1,7 @@
+mod blocks;
+mod bounds;
 mod region;
 mod utils;
 
-pub use region::Region;
+pub use blocks::{Blocks, BlocksIter, RegionIter};
+pub use region::{MergeStrategy, Region, RegionDiff};
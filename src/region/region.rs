@@ -1,20 +1,79 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Index,
+};
 
 use quartz_nbt::{NbtCompound, NbtList, NbtTag};
 
-use crate::{volume::Volume, BlockState, RegionParseError, Vector3};
+use super::{
+    blocks::{Blocks, RegionIter},
+    bounds::BlockBounds,
+};
+use crate::{
+    volume::Volume, Axis, BlockState, CommandParseError, Entity, PaletteRemapError,
+    RegionParseError, RegionWriteError, Vector3,
+};
+
+/// How to resolve positions where both regions have a block when merging one region into another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Replace everything inside the other region's volume, including replacing blocks with the other region's air
+    Overwrite,
+    /// Only place blocks where this region is empty
+    KeepExisting,
+    /// Replace blocks with the other region's blocks, but leave blocks where the other region has air
+    SkipAir,
+}
+
+/// The blocks that differ between two regions, returned by [Region::diff()]
+///
+/// Positions are in the schematic's coordinates, like the ones from [Region::iter()], and each list is sorted by the y, then z, then x coordinates
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegionDiff {
+    /// Positions where the old region has air and the new region has a block, with the new block
+    pub added: Vec<(Vector3<i32>, BlockState)>,
+    /// Positions where the old region has a block and the new region has air, with the old block
+    pub removed: Vec<(Vector3<i32>, BlockState)>,
+    /// Positions where the regions have different blocks, with the old block and then the new block
+    pub changed: Vec<(Vector3<i32>, BlockState, BlockState)>,
+}
+
+impl RegionDiff {
+    /// Check if there aren't any differences
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// The tags in a region's NBT data that get parsed into a [Region], rather than kept in its `extra` tags
+const KNOWN_TAGS: &[&str] = &[
+    "BlockStatePalette",
+    "BlockStates",
+    "Position",
+    "Size",
+    "Entities",
+    "PendingBlockTicks",
+    "PendingFluidTicks",
+    "TileEntities",
+];
 
 /// Represents a region of blocks
+#[derive(Clone)]
 pub struct Region {
     /// The original volume taken up by the region
     ///
     /// May not reflect the actual space taken up when new blocks are inserted into the region. For that, use the [volume()](Region::volume) method
     pub volume: Volume,
-    pub(super) blocks: HashMap<Vector3<i32>, BlockState>,
+    pub(super) blocks: HashMap<Vector3<i32>, usize>,
+    pub(super) palette: Vec<BlockState>,
+    pub(super) palette_indices: HashMap<BlockState, usize>,
     pub(super) entities: Option<NbtList>,
     pub(super) pending_block_ticks: Option<NbtList>,
     pub(super) pending_fluid_ticks: Option<NbtList>,
     pub(super) tile_entities: Option<NbtList>,
+    pub(super) bounds: BlockBounds,
+    /// Tags in the region's NBT data that this crate doesn't know about, which are written back out unchanged when the region is saved
+    pub extra: NbtCompound,
 }
 
 // https://github.com/maruohon/litematica/issues/53#issuecomment-520281566
@@ -24,133 +83,3207 @@ impl Region {
         Region {
             volume: Volume::default(),
             blocks: HashMap::new(),
+            palette: Vec::new(),
+            palette_indices: HashMap::new(),
             entities: None,
             pending_block_ticks: None,
             pending_fluid_ticks: None,
             tile_entities: None,
+            bounds: BlockBounds::default(),
+            extra: NbtCompound::new(),
+        }
+    }
+
+    /// Create a region containing the given blocks, with positions relative to `origin`
+    ///
+    /// The region's stored [volume](Region::volume) starts out empty at `origin`, so its [volume()](Region::volume()) is worked out from the blocks. Air blocks are left out, the same as with [set_block()](Region::set_block)
+    ///
+    /// ```
+    /// # use litematic_editor::{BlockState, Region, Vector3, Volume};
+    /// # use std::collections::HashMap;
+    /// let mut blocks = HashMap::new();
+    /// blocks.insert(Vector3::new(0, 0, 0), BlockState::new("stone", None));
+    /// blocks.insert(Vector3::new(2, 1, 0), BlockState::new("dirt", None));
+    ///
+    /// let region = Region::from_blocks(Vector3::new(10, 0, 10), blocks);
+    ///
+    /// assert_eq!(region.volume(), Volume::new(Vector3::new(10, 0, 10), Vector3::new(3, 2, 1)));
+    /// assert_eq!(region.blocks()[&Vector3::new(2, 1, 0)], "dirt");
+    /// ```
+    pub fn from_blocks(origin: Vector3<i32>, blocks: HashMap<Vector3<i32>, BlockState>) -> Region {
+        let mut region = Region::new();
+
+        region.volume = Volume::new(origin, Vector3::zero());
+
+        for (pos, block) in blocks {
+            region.set_block(pos, block);
         }
+
+        region
+    }
+
+    /// Remove all the blocks, entities, tile entities, and pending ticks from the region, and reset its stored [volume](Region::volume)
+    ///
+    /// The region is left the same as one made with [new()](Region::new), except that its `extra` tags are kept
+    pub fn clear(&mut self) {
+        self.volume = Volume::default();
+        self.blocks.clear();
+        self.palette.clear();
+        self.palette_indices.clear();
+        self.entities = None;
+        self.pending_block_ticks = None;
+        self.pending_fluid_ticks = None;
+        self.tile_entities = None;
+        self.bounds = BlockBounds::default();
     }
 
     /// Calculates the volume taken up by a region including all the blocks in it
+    ///
+    /// The volume is in the schematic's coordinates, and includes the region's stored volume even where there aren't any blocks. Use [world_volume()](Region::world_volume) for just the space taken up by the blocks
+    ///
+    /// The bounding box of the blocks is kept up to date as blocks are set, so this doesn't have to go through every block and is cheap to call repeatedly
     pub fn volume(&self) -> Volume {
-        self.blocks.keys().fold(self.volume, |volume, value| {
-            volume.expand_to_fit(*value + self.volume.origin())
-        })
+        if self.is_empty() {
+            return self.volume;
+        }
+
+        self.volume.expand_to_fit_volume(self.world_volume())
+    }
+
+    /// Convert a position relative to the origin of the region's [volume](Region::volume), like the ones given to [set_block()](Region::set_block), into the schematic's coordinates (world space)
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3, Volume};
+    /// let mut region = Region::new();
+    /// region.volume = Volume::new(Vector3::new(10, 0, -5), Vector3::new(4, 4, 4));
+    /// region.set_block(Vector3::new(1, 2, 3), "stone");
+    ///
+    /// assert_eq!(region.to_world(Vector3::new(1, 2, 3)), Vector3::new(11, 2, -2));
+    /// assert_eq!(region.iter().next().unwrap().0, region.to_world(Vector3::new(1, 2, 3)));
+    /// ```
+    pub fn to_world(&self, local: Vector3<i32>) -> Vector3<i32> {
+        local + self.volume.origin()
+    }
+
+    /// Convert a position in the schematic's coordinates (world space) into a position relative to the origin of the region's [volume](Region::volume), the opposite of [to_world()](Region::to_world)
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3, Volume};
+    /// let mut region = Region::new();
+    /// region.volume = Volume::new(Vector3::new(10, 0, -5), Vector3::new(4, 4, 4));
+    ///
+    /// assert_eq!(region.to_local(Vector3::new(11, 2, -2)), Vector3::new(1, 2, 3));
+    ///
+    /// region.set_block(region.to_local(Vector3::new(10, 0, -5)), "stone");
+    /// assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "stone");
+    /// ```
+    pub fn to_local(&self, world: Vector3<i32>) -> Vector3<i32> {
+        world - self.volume.origin()
+    }
+
+    /// The smallest volume containing all of the region's blocks, in the schematic's coordinates (world space), with a positive size
+    ///
+    /// Regions store their blocks relative to the origin of their [volume](Region::volume), so the positions in [blocks()](Region::blocks) are relative, while the positions from [iter()](Region::iter) and this volume are in the schematic's coordinates. Regions without any blocks get a volume with a size of zero at their origin
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3, Volume};
+    /// let mut region = Region::new();
+    /// region.volume = Volume::new(Vector3::new(10, 0, 10), Vector3::new(16, 16, 16));
+    ///
+    /// assert_eq!(region.world_volume(), Volume::new(Vector3::new(10, 0, 10), Vector3::new(0, 0, 0)));
+    ///
+    /// region.set_block(Vector3::new(1, 2, 3), "stone");
+    /// region.set_block(Vector3::new(2, 2, 3), "stone");
+    ///
+    /// assert_eq!(region.world_volume(), Volume::new(Vector3::new(11, 2, 13), Vector3::new(2, 1, 1)));
+    /// assert!(region.iter().all(|(pos, _)| region.world_volume().contains(pos)));
+    /// ```
+    pub fn world_volume(&self) -> Volume {
+        match self.bounds.volume() {
+            Some(bounds) => bounds.move_to(self.to_world(bounds.origin())),
+            None => Volume::new(self.volume.origin(), Vector3::zero()),
+        }
+    }
+
+    /// Set the region's stored [volume](Region::volume), which decides where the region gets saved along with its blocks
+    ///
+    /// [volume()](Region::volume()) still grows to fit blocks outside of it, so the volume set is the smallest the region can be saved as. Unlike assigning to the field, blocks, entities, tile entities, and pending ticks stay at the same positions in the schematic's coordinates when the origin changes, so their positions relative to the region's origin are moved to make up for it
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3, Volume};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(1, 0, 0), "stone");
+    ///
+    /// region.set_volume(Volume::new(Vector3::new(-2, 0, 0), Vector3::new(5, 5, 5)));
+    ///
+    /// assert_eq!(region.volume(), Volume::new(Vector3::new(-2, 0, 0), Vector3::new(5, 5, 5)));
+    /// assert_eq!(region.iter().next().unwrap().0, Vector3::new(1, 0, 0));
+    /// assert_eq!(region.blocks()[&Vector3::new(3, 0, 0)], "stone");
+    /// ```
+    pub fn set_volume(&mut self, volume: Volume) {
+        let offset = self.volume.origin() - volume.origin();
+
+        self.volume = volume;
+
+        if offset == Vector3::zero() {
+            return;
+        }
+
+        let blocks: HashMap<_, _> = self
+            .blocks
+            .drain()
+            .map(|(pos, index)| (pos + offset, index))
+            .collect();
+
+        self.bounds = BlockBounds::from_positions(blocks.keys());
+        self.blocks = blocks;
+
+        self.offset_entities(offset);
+    }
+
+    /// Shrink the region's stored [volume](Region::volume) to the smallest volume containing all of its blocks, so it gets saved without any empty space around them
+    ///
+    /// This can move the origin of the region's volume, which is done the same way as in [set_volume()](Region::set_volume), so blocks and entities stay at the same positions in the schematic's coordinates but their positions relative to the origin change. Regions without any blocks keep their origin and get a size of zero
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3, Volume};
+    /// let mut region = Region::new();
+    /// region.volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(10, 10, 10));
+    /// region.set_block(Vector3::new(2, 3, 4), "stone");
+    ///
+    /// region.shrink_to_fit();
+    ///
+    /// assert_eq!(region.volume(), Volume::new(Vector3::new(2, 3, 4), Vector3::new(1, 1, 1)));
+    /// assert_eq!(region.iter().next().unwrap().0, Vector3::new(2, 3, 4));
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.set_volume(self.world_volume());
+    }
+
+    /// Check if the region doesn't have any blocks other than air
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3};
+    /// let mut region = Region::new();
+    /// assert!(region.is_empty());
+    ///
+    /// region.set_block(Vector3::new(0, 0, 0), "stone");
+    /// assert!(!region.is_empty());
+    ///
+    /// region.set_block(Vector3::new(0, 0, 0), "air");
+    /// assert!(region.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Count the blocks in the region that aren't air
+    ///
+    /// Air is never stored in a region, so air inside the region's [volume](Region::volume) isn't counted. Use `region.volume().volume()` to count every position including air
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "stone");
+    /// region.set_block(Vector3::new(1, 0, 0), "air");
+    /// region.set_block(Vector3::new(2, 0, 0), "dirt");
+    ///
+    /// assert_eq!(region.non_air_count(), 2);
+    /// assert_eq!(region.volume().volume(), 3);
+    /// ```
+    pub fn non_air_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// The size of the region's [volume()](Region::volume), always positive
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3, Volume};
+    /// let mut region = Region::new();
+    /// region.volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(-2, 3, 1));
+    ///
+    /// assert_eq!(region.dimensions(), Vector3::new(2, 3, 1));
+    ///
+    /// region.set_block(Vector3::new(-1, 0, 4), "stone");
+    ///
+    /// assert_eq!(region.dimensions(), Vector3::new(2, 3, 5));
+    /// ```
+    pub fn dimensions(&self) -> Vector3<i32> {
+        self.volume().make_size_positive().size()
     }
 
     /// Set a block state in the region
     pub fn set_block(&mut self, pos: Vector3<i32>, block: impl Into<BlockState>) {
         let block = block.into();
 
-        if block != BlockState::new("air", None) {
-            self.blocks.insert(pos, block);
+        if block != *BlockState::air() {
+            self.insert_block(pos, block);
         } else {
-            self.blocks.remove(&pos);
+            self.remove_block_at(pos);
+        }
+    }
+
+    /// Remove the block at a position in the region, returning the block that was there or `None` if there was air
+    ///
+    /// Uses the same positions as [set_block()](Region::set_block)
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(1, 2, 3), "stone");
+    ///
+    /// assert_eq!(region.remove_block(Vector3::new(1, 2, 3)).unwrap(), "stone");
+    /// assert_eq!(region.remove_block(Vector3::new(1, 2, 3)), None);
+    /// assert!(region.is_empty());
+    /// ```
+    pub fn remove_block(&mut self, pos: Vector3<i32>) -> Option<BlockState> {
+        self.remove_block_at(pos)
+            .map(|index| self.palette[index].clone())
+    }
+
+    /// Set many block states in the region at once, like calling [set_block()](Region::set_block) for each of them
+    ///
+    /// Reserves space for the blocks up front, which is faster for generators producing lots of blocks
+    ///
+    /// ```
+    /// # use litematic_editor::{BlockState, Region, Vector3};
+    /// let mut region = Region::new();
+    ///
+    /// region.set_blocks_bulk((0..10).map(|x| (Vector3::new(x, 0, 0), BlockState::new("stone", None))));
+    ///
+    /// assert_eq!(region.blocks().len(), 10);
+    /// assert_eq!(region.dimensions(), Vector3::new(10, 1, 1));
+    /// ```
+    pub fn set_blocks_bulk(&mut self, iter: impl IntoIterator<Item = (Vector3<i32>, BlockState)>) {
+        let iter = iter.into_iter();
+
+        self.blocks.reserve(iter.size_hint().0);
+
+        for (pos, block) in iter {
+            self.set_block(pos, block);
+        }
+    }
+
+    /// Insert a block into the block map and the bounds, without checking if it's air
+    fn insert_block(&mut self, pos: Vector3<i32>, block: BlockState) {
+        let index = self.palette_index(block);
+
+        if self.blocks.insert(pos, index).is_none() {
+            self.bounds.add(pos);
+        }
+    }
+
+    /// Remove a block from the block map and the bounds, returning the palette index it had
+    fn remove_block_at(&mut self, pos: Vector3<i32>) -> Option<usize> {
+        let removed = self.blocks.remove(&pos);
+
+        if removed.is_some() {
+            self.bounds.remove(pos);
+        }
+
+        removed
+    }
+
+    /// Get the index of a block state in the region's palette, adding it to the palette if it isn't there yet
+    ///
+    /// Entries stay in the palette even when no blocks use them anymore
+    fn palette_index(&mut self, block: BlockState) -> usize {
+        if let Some(index) = self.palette_indices.get(&block) {
+            return *index;
         }
+
+        self.palette.push(block.clone());
+        self.palette_indices.insert(block, self.palette.len() - 1);
+
+        self.palette.len() - 1
     }
 
     /// A read only map of all the blocks in the region, excluding air blocks
-    pub fn blocks(&self) -> &HashMap<Vector3<i32>, BlockState> {
-        &self.blocks
+    ///
+    /// The positions are relative to the origin of the region's [volume](Region::volume), like the ones given to [set_block()](Region::set_block). Use [iter()](Region::iter) to get positions in the schematic's coordinates instead
+    ///
+    /// Each block is stored as an index into a palette shared by the whole region, so the block states are looked up as they're accessed
+    pub fn blocks(&self) -> Blocks<'_> {
+        Blocks {
+            blocks: &self.blocks,
+            palette: &self.palette,
+        }
     }
 
-    pub(crate) fn new_from_nbt(data: NbtCompound) -> Result<Region, RegionParseError> {
-        let palette =
-            if let NbtTag::List(palette_list) = data.get::<_, &NbtTag>("BlockStatePalette")? {
-                palette_list
-            } else {
-                return Err(RegionParseError::WrongTag("BlockStatePalette".to_string()));
-            };
+    /// An iterator over all the blocks in the region excluding air, with their positions in the schematic's coordinates
+    ///
+    /// The positions are offset by the origin of the region's [volume](Region::volume), unlike the ones in [blocks()](Region::blocks)
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3, Volume};
+    /// let mut region = Region::new();
+    /// region.volume = Volume::new(Vector3::new(10, 0, 10), Vector3::new(1, 1, 1));
+    /// region.set_block(Vector3::new(0, 1, 0), "stone");
+    ///
+    /// let blocks: Vec<_> = region.iter().collect();
+    ///
+    /// assert_eq!(blocks.len(), 1);
+    /// assert_eq!(blocks[0].0, Vector3::new(10, 1, 10));
+    /// assert_eq!(*blocks[0].1, "stone");
+    /// ```
+    pub fn iter(&self) -> RegionIter<'_> {
+        RegionIter {
+            blocks: self.blocks().iter(),
+            origin: self.volume.origin(),
+        }
+    }
 
-        let parsed_palette = Region::parse_palette(palette)?;
+    /// An iterator over every position in the region's [volume()](Region::volume), including air, with their positions in the schematic's coordinates
+    ///
+    /// The positions go in the same order as the packed block states array in a litematic file, increasing the x, then the z, then the y coordinates, which is useful for exporting to formats that store every block
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(1, 0, 0), "stone");
+    ///
+    /// let blocks: Vec<_> = region.iter_dense().map(|(_, block)| block.to_string()).collect();
+    ///
+    /// assert_eq!(blocks, vec!["minecraft:air", "minecraft:stone"]);
+    /// ```
+    pub fn iter_dense(&self) -> impl Iterator<Item = (Vector3<i32>, &BlockState)> {
+        self.volume()
+            .iter()
+            .map(move |pos| (pos, &self[self.to_local(pos)]))
+    }
 
-        let blocks_long_array =
-            if let NbtTag::LongArray(array) = data.get::<_, &NbtTag>("BlockStates")? {
-                array
+    /// A list of all the entities in the region, skipping any entities without an id or a position
+    pub fn entities(&self) -> Vec<Entity> {
+        self.entities
+            .iter()
+            .flatten()
+            .filter_map(|tag| match tag {
+                NbtTag::Compound(entity) => Entity::new_from_nbt(entity),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Add an entity to the region
+    pub fn add_entity(&mut self, entity: Entity) {
+        self.entities.get_or_insert_with(NbtList::new).push(&entity);
+    }
+
+    /// Get the NBT data of the tile entity at the given position, if there is one
+    pub fn tile_entity_at(&self, pos: Vector3<i32>) -> Option<&NbtCompound> {
+        self.tile_entities
+            .iter()
+            .flatten()
+            .filter_map(|tag| match tag {
+                NbtTag::Compound(tile_entity) => Some(tile_entity),
+                _ => None,
+            })
+            .find(|tile_entity| Vector3::from_xyz_nbt(tile_entity).ok() == Some(pos))
+    }
+
+    /// Set the NBT data of the tile entity at the given position, replacing any tile entity that's already there
+    ///
+    /// The `x`, `y`, and `z` tags of the data are set to the position given
+    pub fn set_tile_entity(&mut self, pos: Vector3<i32>, mut data: NbtCompound) {
+        data.insert("x", pos.x);
+        data.insert("y", pos.y);
+        data.insert("z", pos.z);
+
+        let mut tile_entities = NbtList::new();
+
+        for tag in self.tile_entities.iter().flatten() {
+            if let NbtTag::Compound(tile_entity) = tag {
+                if Vector3::from_xyz_nbt(tile_entity).ok() == Some(pos) {
+                    continue;
+                }
+            }
+
+            tile_entities.push(tag.clone());
+        }
+
+        tile_entities.push(data);
+
+        self.tile_entities = Some(tile_entities);
+    }
+
+    /// Move all the entities, tile entities, and pending ticks in the region by the offset given, so they stay lined up with blocks that have been moved by the same amount
+    ///
+    /// Edits these tags of the region's NBT data, leaving anything that doesn't have them alone:
+    /// - `Pos` of each entity in `Entities`, and `TileX`, `TileY`, and `TileZ` of hanging entities like item frames
+    /// - `x`, `y`, and `z` of each tile entity in `TileEntities`
+    /// - `x`, `y`, and `z` of each tick in `PendingBlockTicks` and `PendingFluidTicks`
+    pub fn offset_entities(&mut self, offset: Vector3<i32>) {
+        if let Some(entities) = &self.entities {
+            let mut moved = NbtList::new();
+
+            for tag in entities {
+                match tag {
+                    NbtTag::Compound(entity) => moved.push(Region::offset_entity(entity, offset)),
+                    _ => moved.push(tag.clone()),
+                }
+            }
+
+            self.entities = Some(moved);
+        }
+
+        for list in [
+            &mut self.tile_entities,
+            &mut self.pending_block_ticks,
+            &mut self.pending_fluid_ticks,
+        ] {
+            if let Some(tags) = list {
+                let mut moved = NbtList::new();
+
+                for tag in tags.iter() {
+                    match tag {
+                        NbtTag::Compound(compound) => {
+                            moved.push(Region::offset_int_tags(compound, ["x", "y", "z"], offset))
+                        }
+                        _ => moved.push(tag.clone()),
+                    }
+                }
+
+                *list = Some(moved);
+            }
+        }
+    }
+
+    fn offset_entity(entity: &NbtCompound, offset: Vector3<i32>) -> NbtCompound {
+        let mut entity = Region::offset_int_tags(entity, ["TileX", "TileY", "TileZ"], offset);
+
+        let position = entity.get::<_, &NbtList>("Pos").ok().and_then(|pos| {
+            let coords: Vec<f64> = pos
+                .iter()
+                .filter_map(|tag| match tag {
+                    NbtTag::Double(value) => Some(*value),
+                    _ => None,
+                })
+                .collect();
+
+            if coords.len() == 3 && pos.len() == 3 {
+                Some(coords)
             } else {
-                return Err(RegionParseError::WrongTag("BlockStates".to_string()));
-            };
+                None
+            }
+        });
 
-        let size = Vector3::from_nbt(&data, "Size")?;
+        if let Some(coords) = position {
+            let mut moved = NbtList::new();
 
-        Ok(Region {
-            volume: Volume::new(Vector3::from_nbt(&data, "Position")?, size),
-            blocks: Region::unpack_packed_array(
-                blocks_long_array,
-                &parsed_palette,
-                Region::calculate_bits(parsed_palette.len()),
-                size,
-            ),
-            entities: data.get::<_, &NbtList>("Entities").ok().map(|v| v.clone()),
-            pending_block_ticks: data
-                .get::<_, &NbtList>("PendingBlockTicks")
-                .ok()
-                .map(|v| v.clone()),
-            pending_fluid_ticks: data
-                .get::<_, &NbtList>("PendingFluidTicks")
-                .ok()
-                .map(|v| v.clone()),
-            tile_entities: data
-                .get::<_, &NbtList>("TileEntities")
-                .ok()
-                .map(|v| v.clone()),
-        })
+            moved.push(coords[0] + offset.x as f64);
+            moved.push(coords[1] + offset.y as f64);
+            moved.push(coords[2] + offset.z as f64);
+
+            entity.insert("Pos", moved);
+        }
+
+        entity
     }
 
-    pub(crate) fn to_nbt(&self) -> (NbtCompound, Volume) {
-        let mut out = NbtCompound::new();
+    /// Offset the int tags with the names given, as long as the compound has all three of them
+    fn offset_int_tags(data: &NbtCompound, names: [&str; 3], offset: Vector3<i32>) -> NbtCompound {
+        let mut data = data.clone();
 
-        let palette = Region::generate_palette_nbt(&self.blocks);
+        if let (Ok(x), Ok(y), Ok(z)) = (
+            data.get::<_, i32>(names[0]),
+            data.get::<_, i32>(names[1]),
+            data.get::<_, i32>(names[2]),
+        ) {
+            data.insert(names[0], x + offset.x);
+            data.insert(names[1], y + offset.y);
+            data.insert(names[2], z + offset.z);
+        }
 
-        out.insert(
-            "BlockStatePalette",
-            palette.iter().fold(NbtList::new(), |mut a, v| {
-                a.push(v);
-                a
-            }),
-        );
+        data
+    }
 
-        self.write_misc_data(&mut out);
+    /// Count the blocks in the region equal to the block state given, including its properties
+    pub fn count_of(&self, block: &BlockState) -> usize {
+        self.blocks().values().filter(|v| *v == block).count()
+    }
 
-        let volume = self.volume();
+    /// Count the blocks in the region with the given name, ignoring their properties
+    pub fn count_of_block(&self, name: &str) -> usize {
+        self.blocks().values().filter(|v| *v == name).count()
+    }
 
-        out.insert("Position", volume.origin());
-        out.insert("Size", volume.size());
+    /// Merge the blocks of another region into this one, moving them by the offset given
+    ///
+    /// A block at `pos` in the other region ends up at `pos + offset` in this region
+    pub fn merge_with(&mut self, other: &Region, offset: Vector3<i32>, strategy: MergeStrategy) {
+        if strategy == MergeStrategy::Overwrite {
+            for pos in other.volume() {
+                self.remove_block_at(other.to_local(pos) + offset);
+            }
+        }
 
-        out.insert(
-            "BlockStates",
-            self.generate_block_states_nbt(volume.make_size_positive(), &palette),
-        );
+        for (pos, block) in other.blocks() {
+            let pos = *pos + offset;
+
+            if strategy == MergeStrategy::KeepExisting && self.blocks.contains_key(&pos) {
+                continue;
+            }
 
-        (out, volume)
+            self.insert_block(pos, block.clone());
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Find the blocks that are different in another region, treating this region as the old one and the other region as the new one
+    ///
+    /// The regions are compared in the schematic's coordinates, so regions with different origins are compared by where their blocks actually are. Only blocks are compared, not entities or tile entities
+    ///
+    /// ```
+    /// # use litematic_editor::{BlockState, Region, Vector3};
+    /// let mut old = Region::new();
+    /// old.set_block(Vector3::new(0, 0, 0), "stone");
+    /// old.set_block(Vector3::new(1, 0, 0), "dirt");
+    ///
+    /// let mut new = old.clone();
+    /// new.set_block(Vector3::new(1, 0, 0), "grass_block");
+    /// new.set_block(Vector3::new(2, 0, 0), "stone");
+    ///
+    /// let diff = old.diff(&new);
+    ///
+    /// assert_eq!(diff.added, vec![(Vector3::new(2, 0, 0), BlockState::new("stone", None))]);
+    /// assert!(diff.removed.is_empty());
+    /// assert_eq!(
+    ///     diff.changed,
+    ///     vec![(Vector3::new(1, 0, 0), BlockState::new("dirt", None), BlockState::new("grass_block", None))]
+    /// );
+    /// ```
+    pub fn diff(&self, other: &Region) -> RegionDiff {
+        let mut diff = RegionDiff::default();
 
-    #[test]
-    fn test_new_from_nbt() {
-        let mut root = NbtCompound::new();
+        for (pos, block) in self.iter() {
+            match other.blocks().get(&other.to_local(pos)) {
+                Some(new) if new != block => diff.changed.push((pos, block.clone(), new.clone())),
+                Some(_) => {}
+                None => diff.removed.push((pos, block.clone())),
+            }
+        }
 
-        let mut palette = NbtList::new();
+        for (pos, block) in other.iter() {
+            if !self.blocks.contains_key(&self.to_local(pos)) {
+                diff.added.push((pos, block.clone()));
+            }
+        }
 
-        palette.push(&BlockState::new("bruh", None));
-        palette.push(&BlockState::new("yeet", None));
-        palette.push(&BlockState::new("poggers?", None));
-        palette.push(&BlockState::new("poggers.", None));
+        diff.added.sort_by_key(|(pos, _)| (pos.y, pos.z, pos.x));
+        diff.removed.sort_by_key(|(pos, _)| (pos.y, pos.z, pos.x));
+        diff.changed
+            .sort_by_key(|(pos, _, _)| (pos.y, pos.z, pos.x));
 
-        root.insert("BlockStatePalette", palette);
+        diff
+    }
 
-        root.insert(
-            "BlockStates",
-            vec![0x0123456789abcdef_i64, 0x1032547698badcfe],
-        );
+    /// Apply the changes from a [RegionDiff] to this region, like one returned by [diff()](Region::diff)
+    ///
+    /// Added and changed positions get their new blocks, and removed positions become air. The positions are in the schematic's coordinates, so they're moved by the origin of the region's volume. Setting a position to air removes the block there, the same as [set_block()](Region::set_block)
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3};
+    /// let mut old = Region::new();
+    /// old.set_block(Vector3::new(0, 0, 0), "stone");
+    ///
+    /// let mut new = old.clone();
+    /// new.set_block(Vector3::new(0, 0, 0), "air");
+    /// new.set_block(Vector3::new(0, 1, 0), "dirt");
+    ///
+    /// let diff = old.diff(&new);
+    /// old.apply(&diff);
+    ///
+    /// assert!(old == new);
+    /// ```
+    pub fn apply(&mut self, diff: &RegionDiff) {
+        for (pos, _) in &diff.removed {
+            self.remove_block_at(self.to_local(*pos));
+        }
 
-        root.insert("Size", Vector3::new(4, 4, 4));
-        root.insert("Position", Vector3::new(0, 0, 0));
+        let new_blocks = diff
+            .added
+            .iter()
+            .map(|(pos, block)| (pos, block))
+            .chain(diff.changed.iter().map(|(pos, _, block)| (pos, block)));
+
+        for (pos, block) in new_blocks {
+            self.set_block(self.to_local(*pos), block.clone());
+        }
+    }
+
+    /// Get all the blocks in the region sorted by their distance from the given position, closest first
+    ///
+    /// Blocks that are the same distance away are ordered by their y, then z, then x coordinates
+    pub fn blocks_by_distance(&self, from: Vector3<i32>) -> Vec<(Vector3<i32>, &BlockState)> {
+        let distance_squared = |pos: Vector3<i32>| {
+            let offset = pos - from;
+
+            (offset.x as i64).pow(2) + (offset.y as i64).pow(2) + (offset.z as i64).pow(2)
+        };
+
+        let mut blocks: Vec<_> = self
+            .blocks()
+            .iter()
+            .map(|(pos, block)| (*pos, block))
+            .collect();
+
+        blocks.sort_by_key(|(pos, _)| (distance_squared(*pos), pos.y, pos.z, pos.x));
+
+        blocks
+    }
+
+    /// Fill every empty position inside of the region's [volume()](Region::volume) with the marker block, returning how many blocks were placed
+    ///
+    /// Useful for seeing the empty space inside of a build, for example by marking it with glass
+    pub fn mark_air(&mut self, marker: BlockState) -> usize {
+        if marker == *BlockState::air() {
+            return 0;
+        }
+
+        let mut placed = 0;
+
+        for pos in self.volume() {
+            let pos = self.to_local(pos);
+
+            if !self.blocks.contains_key(&pos) {
+                self.insert_block(pos, marker.clone());
+                placed += 1;
+            }
+        }
+
+        placed
+    }
+
+    /// Replace the block at `start` and every block connected to it through faces that's equal to it with `replacement`, returning how many blocks were replaced
+    ///
+    /// Uses the same positions as [set_block()](Region::set_block). The fill never leaves the region's [volume()](Region::volume), so filling air doesn't spread forever, and replacing with air removes the blocks like [set_block()](Region::set_block) does
+    ///
+    /// ```
+    /// # use litematic_editor::{BlockState, Region, Vector3};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "stone");
+    /// region.set_block(Vector3::new(1, 0, 0), "stone");
+    /// region.set_block(Vector3::new(3, 0, 0), "stone");
+    ///
+    /// let replaced = region.flood_fill(Vector3::new(0, 0, 0), BlockState::new("dirt", None));
+    ///
+    /// assert_eq!(replaced, 2);
+    /// assert_eq!(region.blocks()[&Vector3::new(1, 0, 0)], "dirt");
+    /// assert_eq!(region.blocks()[&Vector3::new(3, 0, 0)], "stone");
+    /// ```
+    pub fn flood_fill(&mut self, start: Vector3<i32>, replacement: BlockState) -> usize {
+        let volume = self.volume().make_size_positive();
+        let bounds = volume.move_to(self.to_local(volume.origin()));
+
+        let target = self.blocks().get(&start).cloned();
+        let replacement = Some(replacement).filter(|v| v != BlockState::air());
+
+        if !bounds.contains(start) || target == replacement {
+            return 0;
+        }
+
+        let mut to_visit = vec![start];
+        let mut replaced = 0;
+
+        while let Some(pos) = to_visit.pop() {
+            if !bounds.contains(pos) || self.blocks().get(&pos) != target.as_ref() {
+                continue;
+            }
+
+            match &replacement {
+                Some(block) => self.insert_block(pos, block.clone()),
+                None => {
+                    self.remove_block_at(pos);
+                }
+            }
+
+            replaced += 1;
+
+            for offset in [
+                Vector3::new(1, 0, 0),
+                Vector3::new(-1, 0, 0),
+                Vector3::new(0, 1, 0),
+                Vector3::new(0, -1, 0),
+                Vector3::new(0, 0, 1),
+                Vector3::new(0, 0, -1),
+            ] {
+                to_visit.push(pos + offset);
+            }
+        }
+
+        replaced
+    }
+
+    /// Get the smallest volume containing every block matching the predicate, or `None` if no blocks match
+    ///
+    /// Uses the same positions as [blocks()](Region::blocks), relative to the origin of the region's volume. The size of the volume returned is always positive
+    pub fn bounds_of(&self, predicate: impl Fn(&BlockState) -> bool) -> Option<Volume> {
+        self.blocks()
+            .iter()
+            .filter(|(_, block)| predicate(block))
+            .fold(None, |bounds: Option<Volume>, (pos, _)| {
+                Some(match bounds {
+                    Some(bounds) => bounds.expand_to_fit(*pos),
+                    None => Volume::new(*pos, Vector3::one()),
+                })
+            })
+    }
+
+    /// The distinct block states used in the region, sorted by name and then by properties
+    ///
+    /// This is the palette the region is saved with, minus the air at the start of it. Air only shows up if it has properties, since plain air is never stored
+    pub fn palette(&self) -> Vec<BlockState> {
+        let mut palette = self.generate_palette().0;
+
+        palette.remove(0);
+
+        palette
+    }
+
+    /// Count the distinct block states inside the given volume, excluding air
+    ///
+    /// The volume uses the same positions as [blocks()](Region::blocks), relative to the origin of the region's volume. Use [to_local()](Region::to_local) to convert a volume's origin from the schematic's coordinates
+    pub fn distinct_blocks_in(&self, volume: Volume) -> usize {
+        self.blocks()
+            .iter()
+            .filter(|(pos, _)| volume.contains(**pos))
+            .map(|(_, block)| block)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Replace every block with the block state the function gives for it, removing blocks that it turns into air
+    ///
+    /// Since blocks are stored as indices into the region's palette, the function is only called once for each distinct block state in the region rather than once for each block
+    ///
+    /// ```
+    /// # use litematic_editor::{BlockState, Region, Vector3};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "oak_planks");
+    /// region.set_block(Vector3::new(1, 0, 0), "oak_log[axis=y]".parse::<BlockState>().unwrap());
+    ///
+    /// region.map_blocks(|block| {
+    ///     let mut block = block.clone();
+    ///     block.set_block(&block.get_block().replace("oak", "spruce"));
+    ///     block
+    /// });
+    ///
+    /// assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "spruce_planks");
+    /// assert_eq!(region.blocks()[&Vector3::new(1, 0, 0)].to_string(), "minecraft:spruce_log[axis=y]");
+    /// ```
+    pub fn map_blocks(&mut self, f: impl Fn(&BlockState) -> BlockState) {
+        let mut used = vec![false; self.palette.len()];
+
+        for index in self.blocks.values() {
+            used[*index] = true;
+        }
+
+        let mut new_indices = vec![None; self.palette.len()];
+
+        for (index, new_index) in new_indices.iter_mut().enumerate() {
+            if !used[index] {
+                continue;
+            }
+
+            let block = f(&self.palette[index]);
+
+            if block != *BlockState::air() {
+                *new_index = Some(self.palette_index(block));
+            }
+        }
+
+        let mut removed = Vec::new();
+
+        for (pos, index) in self.blocks.iter_mut() {
+            match new_indices[*index] {
+                Some(new_index) => *index = new_index,
+                None => removed.push(*pos),
+            }
+        }
+
+        for pos in removed {
+            self.remove_block_at(pos);
+        }
+    }
+
+    /// Only keep the blocks the predicate returns true for, like [HashMap::retain()]
+    ///
+    /// The predicate is given positions in the schematic's coordinates, the same as [iter()](Region::iter)
+    pub fn retain(&mut self, predicate: impl Fn(Vector3<i32>, &BlockState) -> bool) {
+        let removed: Vec<_> = self
+            .blocks()
+            .iter()
+            .filter(|(pos, block)| !predicate(self.to_world(**pos), block))
+            .map(|(pos, _)| *pos)
+            .collect();
+
+        for pos in removed {
+            self.remove_block_at(pos);
+        }
+    }
+
+    /// Rename blocks according to the mapping given from old block ids to new ones, keeping their properties, and returning how many blocks were renamed
+    ///
+    /// Useful for blocks that were renamed between minecraft versions. Both sides of the mapping are prefixed with `minecraft:` if it's left out and converted to lowercase, the same way as in [BlockState::new()]
+    ///
+    /// ```
+    /// # use litematic_editor::{BlockState, Region, Vector3};
+    /// # use std::collections::HashMap;
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "grass_path");
+    ///
+    /// let mut mapping = HashMap::new();
+    /// mapping.insert("grass_path".to_string(), "dirt_path".to_string());
+    ///
+    /// assert_eq!(region.remap_ids(&mapping), 1);
+    /// assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "dirt_path");
+    /// ```
+    pub fn remap_ids(&mut self, mapping: &HashMap<String, String>) -> usize {
+        let mapping: HashMap<_, _> = mapping
+            .iter()
+            .map(|(from, to)| {
+                (
+                    BlockState::prefix_block_name(from),
+                    BlockState::prefix_block_name(to),
+                )
+            })
+            .collect();
+
+        let renamed = self
+            .blocks()
+            .values()
+            .filter(|block| mapping.contains_key(block.get_block()))
+            .count();
+
+        self.map_blocks(|block| {
+            let mut block = block.clone();
+
+            if let Some(new_id) = mapping.get(block.get_block()) {
+                block.set_block(new_id);
+            }
+
+            block
+        });
+
+        renamed
+    }
+
+    /// Replace every block equal to `from` with `to`, returning how many blocks were replaced
+    ///
+    /// Block states only count as equal when their properties are too. Replacing with air removes the blocks
+    ///
+    /// ```
+    /// # use litematic_editor::{BlockState, Region, Vector3};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "stone");
+    /// region.set_block(Vector3::new(0, 1, 0), "stone");
+    /// region.set_block(Vector3::new(0, 2, 0), "dirt");
+    ///
+    /// assert_eq!(region.replace_block(&BlockState::new("stone", None), BlockState::new("granite", None)), 2);
+    /// assert_eq!(region.count_of_block("granite"), 2);
+    /// ```
+    pub fn replace_block(&mut self, from: &BlockState, to: BlockState) -> usize {
+        // Palettes read from files can contain the same block state more than once
+        let matches: Vec<_> = self.palette.iter().map(|block| block == from).collect();
+
+        let positions: Vec<_> = self
+            .blocks
+            .iter()
+            .filter(|(_, index)| matches[**index])
+            .map(|(pos, _)| *pos)
+            .collect();
+
+        if to == *BlockState::air() {
+            for pos in positions.iter() {
+                self.remove_block_at(*pos);
+            }
+        } else {
+            let to_index = self.palette_index(to);
+
+            for pos in positions.iter() {
+                self.blocks.insert(*pos, to_index);
+            }
+        }
+
+        positions.len()
+    }
+
+    /// Replace every block equal to `from` on the layer at the given y coordinate with `to`, returning how many blocks were replaced
+    ///
+    /// `y` is relative to the origin of the region's volume, like the positions given to [set_block()](Region::set_block). Use [to_local()](Region::to_local) to convert a y coordinate from the schematic's coordinates
+    pub fn replace_layer(&mut self, y: i32, from: &BlockState, to: BlockState) -> usize {
+        let positions: Vec<_> = self
+            .blocks()
+            .iter()
+            .filter(|(pos, block)| pos.y == y && *block == from)
+            .map(|(pos, _)| *pos)
+            .collect();
+
+        for pos in positions.iter() {
+            self.set_block(*pos, to.clone());
+        }
+
+        positions.len()
+    }
+
+    /// Swap out every block for another entry of the region's palette, where `mapping[i]` is the new palette index for the blocks using palette index `i`
+    ///
+    /// The palette is the one used when saving the region: index 0 is air, followed by the distinct block states in the region sorted by name and then properties. Since air is never stored, `mapping[0]` is ignored, and blocks mapped to index 0 are removed
+    pub fn remap_palette_indices(&mut self, mapping: &[usize]) -> Result<(), PaletteRemapError> {
+        let (palette, palette_mapping) = self.generate_palette();
+
+        if mapping.len() != palette.len() {
+            return Err(PaletteRemapError::WrongLength {
+                expected: palette.len(),
+                found: mapping.len(),
+            });
+        }
+
+        if let Some(index) = mapping.iter().find(|v| **v >= palette.len()) {
+            return Err(PaletteRemapError::IndexOutOfRange(*index));
+        }
+
+        let blocks: Vec<_> = self
+            .blocks
+            .iter()
+            .map(|(pos, index)| (*pos, palette_mapping[*index]))
+            .collect();
+
+        for (pos, index) in blocks {
+            self.set_block(pos, palette[mapping[index]].clone());
+        }
+
+        Ok(())
+    }
+
+    /// Build a stepped square pyramid with its base layer centered on `base_center`
+    ///
+    /// Each layer above the base is two blocks narrower than the one below it, until the layers run out of blocks
+    pub fn fill_pyramid(&mut self, base_center: Vector3<i32>, base_size: i32, block: BlockState) {
+        let corner = base_center - Vector3::new(base_size / 2, 0, base_size / 2);
+
+        let mut layer = 0;
+
+        while base_size - 2 * layer > 0 {
+            let size = base_size - 2 * layer;
+            let origin = corner + Vector3::new(layer, layer, layer);
+
+            for pos in Volume::new(origin, Vector3::new(size, 1, size)) {
+                self.set_block(pos, block.clone());
+            }
+
+            layer += 1;
+        }
+    }
+
+    /// Copy every block to its reflection across the plane perpendicular to `axis` at the coordinate `coord`, making the region symmetric
+    ///
+    /// Unlike moving the blocks, the original blocks stay where they are. The plane goes through the middle of the blocks at `coord`, and the copied blocks' properties are flipped the same way as in [BlockState::mirrored()]. Blocks on the plane are their own reflection, so they're left alone. Other positions that already have a block are overwritten by the reflection of the block opposite them
+    ///
+    /// `coord` is relative to the origin of the region's [volume](Region::volume), like the positions given to [set_block()](Region::set_block)
+    pub fn mirror_copy(&mut self, axis: Axis, coord: i32) {
+        let blocks: Vec<_> = self
+            .blocks()
+            .iter()
+            .map(|(pos, block)| (*pos, block.mirrored(axis)))
+            .collect();
+
+        for (pos, block) in blocks {
+            let mut reflected = pos;
+
+            match axis {
+                Axis::X => reflected.x = 2 * coord - pos.x,
+                Axis::Y => reflected.y = 2 * coord - pos.y,
+                Axis::Z => reflected.z = 2 * coord - pos.z,
+            }
+
+            if reflected != pos {
+                self.insert_block(reflected, block);
+            }
+        }
+    }
+
+    /// Reflect the region's blocks across the middle of its [volume](Region::volume) along the axis given, keeping the region in the same place
+    ///
+    /// The blocks' properties are flipped the same way as in [BlockState::mirrored()]. Entities and tile entities aren't moved
+    ///
+    /// ```
+    /// # use litematic_editor::{Axis, BlockState, Region, Vector3};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "oak_stairs[facing=east,shape=outer_left]".parse::<BlockState>().unwrap());
+    /// region.set_block(Vector3::new(2, 0, 0), "stone");
+    ///
+    /// region.mirror(Axis::X);
+    ///
+    /// assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "stone");
+    /// assert_eq!(region.blocks()[&Vector3::new(2, 0, 0)], "oak_stairs[facing=west,shape=outer_right]".parse::<BlockState>().unwrap());
+    /// ```
+    pub fn mirror(&mut self, axis: Axis) {
+        let volume = self.volume().make_size_positive();
+        let low = self.to_local(volume.origin());
+        let high = low + volume.size() - Vector3::new(1, 1, 1);
+
+        let blocks: Vec<_> = self
+            .blocks()
+            .iter()
+            .map(|(pos, block)| {
+                let mut pos = *pos;
+
+                match axis {
+                    Axis::X => pos.x = low.x + high.x - pos.x,
+                    Axis::Y => pos.y = low.y + high.y - pos.y,
+                    Axis::Z => pos.z = low.z + high.z - pos.z,
+                }
+
+                (pos, block.mirrored(axis))
+            })
+            .collect();
+
+        self.blocks.clear();
+        self.bounds = BlockBounds::default();
+
+        for (pos, block) in blocks {
+            self.insert_block(pos, block);
+        }
+    }
+
+    /// Rotate the region's blocks clockwise around the y axis by the amount of quarter turns given, as seen from above, keeping the lowest corner of its [volume](Region::volume) in the same place
+    ///
+    /// Negative amounts rotate counterclockwise. The volume's size along x and z is swapped for odd amounts of quarter turns, and the blocks' properties are rotated the same way as in [BlockState::rotated()]. The region's volume is given a positive size first, the same way as with [set_volume()](Region::set_volume). Entities and tile entities aren't rotated
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3, Volume};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "furnace[facing=north]".parse::<litematic_editor::BlockState>().unwrap());
+    /// region.set_block(Vector3::new(2, 0, 0), "stone");
+    ///
+    /// region.rotate(1);
+    ///
+    /// assert_eq!(region.volume(), Volume::new(Vector3::new(0, 0, 0), Vector3::new(1, 1, 3)));
+    /// assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)].properties["facing"], "east");
+    /// assert_eq!(region.blocks()[&Vector3::new(0, 0, 2)], "stone");
+    /// ```
+    pub fn rotate(&mut self, quarter_turns: i32) {
+        self.set_volume(self.volume().make_size_positive());
+
+        let turns = quarter_turns.rem_euclid(4);
+        let mut size = self.volume.size();
+
+        let mut blocks: Vec<_> = self
+            .blocks()
+            .iter()
+            .map(|(pos, block)| (*pos, block.rotated(turns)))
+            .collect();
+
+        for _ in 0..turns {
+            for (pos, _) in blocks.iter_mut() {
+                *pos = Vector3::new(size.z - 1 - pos.z, pos.y, pos.x);
+            }
+
+            size = Vector3::new(size.z, size.y, size.x);
+        }
+
+        self.volume = Volume::new(self.volume.origin(), size);
+        self.blocks.clear();
+        self.bounds = BlockBounds::default();
+
+        for (pos, block) in blocks {
+            self.insert_block(pos, block);
+        }
+    }
+
+    /// Generate a `/setblock` command for every block in the region, for placing it without the mod
+    ///
+    /// Each block at `pos` is placed at `pos + origin`. The commands are ordered by y, then z, then x so lower blocks are placed first
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3};
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "stone");
+    ///
+    /// assert_eq!(region.to_setblock_commands(Vector3::new(10, 64, 10)), vec!["/setblock 10 64 10 minecraft:stone"]);
+    /// ```
+    pub fn to_setblock_commands(&self, origin: Vector3<i32>) -> Vec<String> {
+        let mut blocks: Vec<_> = self.blocks().iter().collect();
+
+        blocks.sort_by_key(|(pos, _)| (pos.y, pos.z, pos.x));
+
+        blocks
+            .into_iter()
+            .map(|(pos, block)| {
+                let pos = *pos + origin;
+
+                format!("/setblock {} {} {} {}", pos.x, pos.y, pos.z, block)
+            })
+            .collect()
+    }
+
+    /// Build a region from a list of `/setblock` and `/fill` commands, placing each block at the coordinates the command gives
+    ///
+    /// Later commands overwrite earlier ones. Empty lines are skipped, and the leading `/` is optional. The `replace`, `keep`, and `destroy` modes are supported, but `/fill` commands using `hollow`, `outline`, or a replace filter aren't, and neither are relative `~` or local `^` coordinates
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Vector3};
+    /// let region = Region::from_setblock_commands(&[
+    ///     "/fill 0 0 0 2 0 2 minecraft:stone",
+    ///     "/setblock 1 0 1 minecraft:observer[facing=up]",
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(region.blocks().len(), 9);
+    /// assert_eq!(region.blocks()[&Vector3::new(1, 0, 1)], "observer");
+    /// ```
+    pub fn from_setblock_commands(lines: &[&str]) -> Result<Region, CommandParseError> {
+        let mut region = Region::new();
+
+        for line in lines {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let args: Vec<_> = line.trim_start_matches('/').split_whitespace().collect();
+
+            let (volume, block, mode) = match args.as_slice() {
+                ["setblock", coords @ .., block] | ["setblock", coords @ .., block, _]
+                    if coords.len() == 3 =>
+                {
+                    let pos = Region::parse_command_coords(line, coords)?;
+
+                    (Volume::new(pos, Vector3::new(1, 1, 1)), block, args.get(5))
+                }
+                ["fill", coords @ .., block] | ["fill", coords @ .., block, _]
+                    if coords.len() == 6 =>
+                {
+                    let pos1 = Region::parse_command_coords(line, &coords[..3])?;
+                    let pos2 = Region::parse_command_coords(line, &coords[3..])?;
+
+                    let corner =
+                        Vector3::new(pos1.x.min(pos2.x), pos1.y.min(pos2.y), pos1.z.min(pos2.z));
+                    let size = Vector3::new(
+                        (pos1.x - pos2.x).abs() + 1,
+                        (pos1.y - pos2.y).abs() + 1,
+                        (pos1.z - pos2.z).abs() + 1,
+                    );
+
+                    (Volume::new(corner, size), block, args.get(8))
+                }
+                [command, ..] if *command != "setblock" && *command != "fill" => {
+                    return Err(CommandParseError::UnsupportedCommand(line.to_string()))
+                }
+                _ => return Err(CommandParseError::MalformedCommand(line.to_string())),
+            };
+
+            let keep = match mode.copied() {
+                None | Some("replace") | Some("destroy") => false,
+                Some("keep") => true,
+                Some(_) => return Err(CommandParseError::MalformedCommand(line.to_string())),
+            };
+
+            let block = block.parse::<BlockState>()?;
+
+            for pos in volume {
+                if !keep || !region.blocks.contains_key(&pos) {
+                    region.set_block(pos, block.clone());
+                }
+            }
+        }
+
+        Ok(region)
+    }
+
+    fn parse_command_coords(
+        line: &str,
+        coords: &[&str],
+    ) -> Result<Vector3<i32>, CommandParseError> {
+        let mut parsed = [0; 3];
+
+        for (i, coord) in coords.iter().enumerate() {
+            if coord.starts_with('~') || coord.starts_with('^') {
+                return Err(CommandParseError::RelativeCoordinates(line.to_string()));
+            }
+
+            parsed[i] = coord
+                .parse()
+                .map_err(|_| CommandParseError::MalformedCommand(line.to_string()))?;
+        }
+
+        Ok(Vector3::from_slice(parsed))
+    }
+
+    /// Convert the region to a vanilla structure, the format used by structure blocks
+    ///
+    /// Positions are relative to the lowest corner of the region's [volume()](Region::volume). Air isn't written to the structure, so placing it leaves whatever was there before in the empty positions. Block entities are attached to their blocks, but entities aren't exported. The `DataVersion` tag isn't included since regions don't know it, use [Schematic::to_structure_nbt()](crate::Schematic::to_structure_nbt) for that
+    pub fn to_structure_nbt(&self) -> NbtCompound {
+        let volume = self.volume().make_size_positive();
+        let offset = self.volume.origin() - volume.origin();
+
+        let (palette, mapping) = self.generate_palette();
+
+        let mut tile_entities = HashMap::new();
+
+        for tile_entity in self.tile_entities.iter().flatten() {
+            if let NbtTag::Compound(tile_entity) = tile_entity {
+                if let Ok(pos) = Vector3::<i32>::from_xyz_nbt(tile_entity) {
+                    let mut data = NbtCompound::new();
+
+                    for (name, tag) in tile_entity.inner() {
+                        if name != "x" && name != "y" && name != "z" {
+                            data.insert(name, tag.clone());
+                        }
+                    }
+
+                    tile_entities.insert(pos, data);
+                }
+            }
+        }
+
+        let mut blocks = NbtList::new();
+
+        for (pos, index) in self.blocks.iter() {
+            let mut block_nbt = NbtCompound::new();
+
+            block_nbt.insert("pos", (*pos + offset).to_nbt_list());
+            block_nbt.insert("state", mapping[*index] as i32);
+
+            if let Some(data) = tile_entities.remove(pos) {
+                block_nbt.insert("nbt", data);
+            }
+
+            blocks.push(block_nbt);
+        }
+
+        let mut out = NbtCompound::new();
+
+        out.insert("size", volume.size().to_nbt_list());
+        out.insert(
+            "palette",
+            palette.iter().fold(NbtList::new(), |mut a, v| {
+                a.push(v);
+                a
+            }),
+        );
+        out.insert("blocks", blocks);
+        out.insert("entities", NbtList::new());
+
+        out
+    }
+
+    /// Read a region from a vanilla structure, the format used by structure blocks
+    ///
+    /// The region is placed at (0, 0, 0). Block entity data is kept with its block, but entities aren't imported. Only the first palette is used for structures with several random palettes
+    pub fn from_structure_nbt(data: &NbtCompound) -> Result<Region, RegionParseError> {
+        let size = Vector3::from_nbt_list(data.get::<_, &NbtList>("size")?)
+            .ok_or_else(|| RegionParseError::WrongTag("size".to_string()))?;
+
+        let palette = match data.get::<_, &NbtList>("palette") {
+            Ok(palette) => palette,
+            Err(_) => match data.get::<_, &NbtList>("palettes")?.into_iter().next() {
+                Some(NbtTag::List(palette)) => palette,
+                _ => return Err(RegionParseError::WrongTag("palettes".to_string())),
+            },
+        };
+
+        let parsed_palette = Region::parse_palette(palette)?;
+
+        let mut region = Region::new();
+
+        region.volume = Volume::new(Vector3::default(), size);
+
+        let mut tile_entities = NbtList::new();
+
+        for block in data.get::<_, &NbtList>("blocks")? {
+            let block = if let NbtTag::Compound(block) = block {
+                block
+            } else {
+                return Err(RegionParseError::WrongTag("blocks".to_string()));
+            };
+
+            let pos = Vector3::from_nbt_list(block.get::<_, &NbtList>("pos")?)
+                .ok_or_else(|| RegionParseError::WrongTag("pos".to_string()))?;
+
+            let state = parsed_palette
+                .get(block.get::<_, i32>("state")? as usize)
+                .ok_or(RegionParseError::MalformedBlockData)?;
+
+            region.set_block(pos, state.clone());
+
+            if let Ok(nbt) = block.get::<_, &NbtCompound>("nbt") {
+                let mut tile_entity = nbt.clone();
+
+                tile_entity.insert("x", pos.x);
+                tile_entity.insert("y", pos.y);
+                tile_entity.insert("z", pos.z);
+
+                tile_entities.push(tile_entity);
+            }
+        }
+
+        if !tile_entities.is_empty() {
+            region.tile_entities = Some(tile_entities);
+        }
+
+        Ok(region)
+    }
+
+    pub(crate) fn new_from_nbt(data: &NbtCompound) -> Result<Region, RegionParseError> {
+        let palette =
+            if let NbtTag::List(palette_list) = data.get::<_, &NbtTag>("BlockStatePalette")? {
+                palette_list
+            } else {
+                return Err(RegionParseError::WrongTag("BlockStatePalette".to_string()));
+            };
+
+        let parsed_palette = Region::parse_palette(palette)?;
+
+        if parsed_palette.is_empty() {
+            return Err(RegionParseError::EmptyPalette);
+        }
+
+        let blocks_long_array =
+            if let NbtTag::LongArray(array) = data.get::<_, &NbtTag>("BlockStates")? {
+                array
+            } else {
+                return Err(RegionParseError::WrongTag("BlockStates".to_string()));
+            };
+
+        let size = Vector3::from_nbt(data, "Size")?;
+
+        // Negative sizes are allowed, they mean the region extends in the negative direction from its position. Empty regions are written with a size of zero and no block states
+        if (size.x == 0 || size.y == 0 || size.z == 0) && !blocks_long_array.is_empty() {
+            return Err(RegionParseError::InvalidDimensions(size));
+        }
+
+        let (position, size) =
+            Region::normalize_litematica_volume(Vector3::from_nbt(data, "Position")?, size)?;
+
+        let block_count = Region::checked_block_count(position, size)?;
+
+        let bits = Region::calculate_bits(parsed_palette.len());
+        let expected_longs = Region::calculate_amt_of_longs(block_count, bits) as usize;
+
+        if blocks_long_array.len() != expected_longs {
+            return Err(RegionParseError::BlockStatesLengthMismatch {
+                expected: expected_longs,
+                found: blocks_long_array.len(),
+            });
+        }
+
+        let blocks = Region::unpack_packed_array(blocks_long_array, &parsed_palette, bits, size)?;
+
+        let mut palette_indices = HashMap::new();
+
+        for (index, block) in parsed_palette.iter().enumerate() {
+            palette_indices.entry(block.clone()).or_insert(index);
+        }
+
+        let mut extra = NbtCompound::new();
+
+        for (key, tag) in data.inner() {
+            if !KNOWN_TAGS.contains(&key.as_str()) {
+                extra.insert(key.clone(), tag.clone());
+            }
+        }
+
+        Ok(Region {
+            volume: Volume::new(position, size),
+            bounds: BlockBounds::from_positions(blocks.keys()),
+            blocks,
+            palette: parsed_palette,
+            palette_indices,
+            entities: data.get::<_, &NbtList>("Entities").ok().map(|v| v.clone()),
+            pending_block_ticks: data
+                .get::<_, &NbtList>("PendingBlockTicks")
+                .ok()
+                .map(|v| v.clone()),
+            pending_fluid_ticks: data
+                .get::<_, &NbtList>("PendingFluidTicks")
+                .ok()
+                .map(|v| v.clone()),
+            tile_entities: data
+                .get::<_, &NbtList>("TileEntities")
+                .ok()
+                .map(|v| v.clone()),
+            extra,
+        })
+    }
+
+    pub(crate) fn new_from_schem(data: &NbtCompound) -> Result<Region, RegionParseError> {
+        let mut palette = HashMap::new();
+
+        for (name, index) in data.get::<_, &NbtCompound>("Palette")?.inner() {
+            if let NbtTag::Int(index) = index {
+                palette.insert(*index as usize, name.parse::<BlockState>()?);
+            } else {
+                return Err(RegionParseError::WrongTag("Palette".to_string()));
+            }
+        }
+
+        let block_data = if let NbtTag::ByteArray(array) = data.get::<_, &NbtTag>("BlockData")? {
+            array
+        } else {
+            return Err(RegionParseError::WrongTag("BlockData".to_string()));
+        };
+
+        // The dimensions are stored as shorts but are meant to be read as unsigned
+        let size = Vector3::new(
+            data.get::<_, i16>("Width")? as u16 as i32,
+            data.get::<_, i16>("Height")? as u16 as i32,
+            data.get::<_, i16>("Length")? as u16 as i32,
+        );
+
+        let offset = match data.get::<_, &NbtTag>("Offset").ok() {
+            Some(NbtTag::IntArray(array)) if array.len() == 3 => {
+                Vector3::new(array[0], array[1], array[2])
+            }
+            Some(_) => return Err(RegionParseError::WrongTag("Offset".to_string())),
+            None => Vector3::default(),
+        };
+
+        let block_count = Region::checked_block_count(offset, size)?;
+
+        let indices = Region::unpack_varint_array(block_data)?;
+
+        if indices.len() != block_count as usize {
+            return Err(RegionParseError::MalformedBlockData);
+        }
+
+        let mut region = Region::new();
+
+        region.volume = Volume::new(offset, size);
+
+        for (position, index) in indices.into_iter().enumerate() {
+            let block = palette
+                .get(&index)
+                .ok_or(RegionParseError::MalformedBlockData)?;
+
+            region.set_block(
+                Region::index_to_coords(size, position as u64).unwrap(),
+                block.clone(),
+            );
+        }
+
+        Ok(region)
+    }
+
+    /// The amount of blocks in a volume read from a file, erroring instead of overflowing when the size is too large for the amount to fit in an `i32`, or when the far corner of the volume doesn't fit in an `i32`
+    fn checked_block_count(
+        origin: Vector3<i32>,
+        size: Vector3<i32>,
+    ) -> Result<i32, RegionParseError> {
+        let corner_fits = origin.x.checked_add(size.x).is_some()
+            && origin.y.checked_add(size.y).is_some()
+            && origin.z.checked_add(size.z).is_some();
+
+        match size.checked_volume() {
+            Some(count) if corner_fits => Ok(count),
+            _ => Err(RegionParseError::InvalidDimensions(size)),
+        }
+    }
+
+    /// Convert a region's position and size as litematica stores them into the lowest corner of the region and a positive size
+    ///
+    /// Along axes where the size is negative, the position is the block in the corner with the highest coordinate, so the region covers the blocks from `position + size + 1` up to `position`. Blocks, entities, and tile entities are always stored relative to the lowest corner
+    ///
+    /// Errors if the lowest corner or the positive size doesn't fit in an `i32`
+    fn normalize_litematica_volume(
+        position: Vector3<i32>,
+        size: Vector3<i32>,
+    ) -> Result<(Vector3<i32>, Vector3<i32>), RegionParseError> {
+        let normalize = |position: i32, size: i32| {
+            if size < 0 {
+                Some((position.checked_add(size + 1)?, size.checked_neg()?))
+            } else {
+                Some((position, size))
+            }
+        };
+
+        let invalid = || RegionParseError::InvalidDimensions(size);
+
+        let (x, size_x) = normalize(position.x, size.x).ok_or_else(invalid)?;
+        let (y, size_y) = normalize(position.y, size.y).ok_or_else(invalid)?;
+        let (z, size_z) = normalize(position.z, size.z).ok_or_else(invalid)?;
+
+        Ok((Vector3::new(x, y, z), Vector3::new(size_x, size_y, size_z)))
+    }
+
+    pub(crate) fn to_nbt(&self) -> Result<(NbtCompound, Volume), RegionWriteError> {
+        let mut out = self.extra.clone();
+
+        let (palette, mapping) = self.generate_palette();
+
+        out.insert(
+            "BlockStatePalette",
+            palette.iter().fold(NbtList::new(), |mut a, v| {
+                a.push(v);
+                a
+            }),
+        );
+
+        self.write_misc_data(&mut out);
+
+        // Always written with a positive size, since litematica reads negative sizes as starting one block further along than volumes do
+        let volume = self.volume().make_size_positive();
+
+        out.insert("Position", volume.origin());
+        out.insert("Size", volume.size());
+
+        let bits = Region::calculate_bits_checked(palette.len())?;
+
+        let block_states = self.generate_block_states_nbt(volume, bits, &mapping)?;
+
+        Region::validate_block_states_length(&block_states, volume.volume(), bits)?;
+
+        out.insert("BlockStates", block_states);
+
+        Ok((out, volume))
+    }
+}
+
+/// Get the block at a position in the region, or air if there's no block there
+///
+/// There's no `IndexMut` implementation since air isn't stored in the region, so blocks still have to be changed with [set_block()](Region::set_block)
+///
+/// ```
+/// # use litematic_editor::{Region, Vector3};
+/// let mut region = Region::new();
+/// region.set_block(Vector3::new(0, 0, 0), "stone");
+///
+/// assert_eq!(region[Vector3::new(0, 0, 0)], "stone");
+/// assert_eq!(region[Vector3::new(1, 0, 0)], "air");
+/// ```
+impl Index<Vector3<i32>> for Region {
+    type Output = BlockState;
+
+    fn index(&self, pos: Vector3<i32>) -> &BlockState {
+        self.blocks().get(&pos).unwrap_or_else(|| BlockState::air())
+    }
+}
+
+/// Iterate over the blocks in the region excluding air, with their positions in the schematic's coordinates, the same as [Region::iter()]
+///
+/// ```
+/// # use litematic_editor::{Region, Vector3, Volume};
+/// let mut region = Region::new();
+/// region.volume = Volume::new(Vector3::new(0, 64, 0), Vector3::new(1, 1, 1));
+/// region.set_block(Vector3::new(0, 0, 0), "stone");
+///
+/// for (pos, block) in &region {
+///     assert_eq!(pos, Vector3::new(0, 64, 0));
+///     assert_eq!(*block, "stone");
+/// }
+/// ```
+impl<'a> IntoIterator for &'a Region {
+    type Item = (Vector3<i32>, &'a BlockState);
+    type IntoIter = RegionIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Two regions are equal when they have the same blocks, entities, tile entities, pending ticks, and extra tags, and the same computed [volume()](Region::volume)
+///
+/// The stored [volume](Region::volume) field isn't compared directly. Since blocks are stored relative to its origin, its origin still matters, but a region whose stored size is smaller than its blocks is equal to one whose stored size already fits them
+impl PartialEq for Region {
+    fn eq(&self, other: &Region) -> bool {
+        self.volume() == other.volume()
+            && self.blocks().len() == other.blocks().len()
+            && self
+                .blocks()
+                .iter()
+                .all(|(pos, block)| other.blocks().get(pos) == Some(block))
+            && self.entities == other.entities
+            && self.pending_block_ticks == other.pending_block_ticks
+            && self.pending_fluid_ticks == other.pending_fluid_ticks
+            && self.tile_entities == other.tile_entities
+            && self.extra == other.extra
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_from_nbt() {
+        let mut root = NbtCompound::new();
+
+        let mut palette = NbtList::new();
+
+        palette.push(&BlockState::new("bruh", None));
+        palette.push(&BlockState::new("yeet", None));
+        palette.push(&BlockState::new("poggers?", None));
+        palette.push(&BlockState::new("poggers.", None));
+
+        root.insert("BlockStatePalette", palette);
+
+        root.insert(
+            "BlockStates",
+            vec![0x0123456789abcdef_i64, 0x1032547698badcfe],
+        );
+
+        root.insert("Size", Vector3::new(4, 4, 4));
+        root.insert("Position", Vector3::new(0, 0, 0));
+
+        Region::new_from_nbt(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_from_nbt_negative_size() {
+        let mut root = NbtCompound::new();
+
+        let mut palette = NbtList::new();
+        palette.push(&BlockState::new("air", None));
+        palette.push(&BlockState::new("stone", None));
+        palette.push(&BlockState::new("dirt", None));
+
+        root.insert("BlockStatePalette", palette);
+        // Stone in the first position and dirt in the third, with two bits each
+        root.insert("BlockStates", vec![0b10_00_01_i64]);
+        root.insert("Size", Vector3::new(-3, 1, 2));
+        root.insert("Position", Vector3::new(5, 0, 0));
+
+        let region = Region::new_from_nbt(&root).unwrap();
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(3, 0, 0), Vector3::new(3, 1, 2))
+        );
+
+        let mut blocks: Vec<_> = region
+            .iter()
+            .map(|(pos, block)| (pos, block.clone()))
+            .collect();
+        blocks.sort_by_key(|(pos, _)| pos.x);
+
+        assert_eq!(
+            blocks,
+            vec![
+                (Vector3::new(3, 0, 0), BlockState::new("stone", None)),
+                (Vector3::new(5, 0, 0), BlockState::new("dirt", None)),
+            ]
+        );
+
+        let (nbt, _) = region.to_nbt().unwrap();
+
+        assert_eq!(
+            Vector3::<i32>::from_nbt(&nbt, "Position").unwrap(),
+            Vector3::new(3, 0, 0)
+        );
+        assert_eq!(
+            Vector3::<i32>::from_nbt(&nbt, "Size").unwrap(),
+            Vector3::new(3, 1, 2)
+        );
+        assert!(Region::new_from_nbt(&nbt).unwrap() == region);
+    }
+
+    #[test]
+    fn test_to_nbt_negative_volume() {
+        let mut region = Region::new();
+        region.volume = Volume::new(Vector3::new(4, 0, 0), Vector3::new(-4, 2, 2));
+        region.set_block(Vector3::new(-4, 0, 0), "stone");
+        region.set_block(Vector3::new(-1, 1, 1), "dirt");
+
+        let (nbt, _) = region.to_nbt().unwrap();
+        let parsed = Region::new_from_nbt(&nbt).unwrap();
+
+        assert_eq!(parsed.volume(), region.volume().make_size_positive());
+        assert!(parsed.diff(&region).is_empty());
+    }
+
+    #[test]
+    fn test_new_from_nbt_invalid() {
+        let region_nbt = |palette: NbtList, size: Vector3<i32>| {
+            let mut root = NbtCompound::new();
+
+            root.insert("BlockStatePalette", palette);
+            root.insert("BlockStates", vec![0_i64; 2]);
+            root.insert("Size", size);
+            root.insert("Position", Vector3::new(0, 0, 0));
+
+            root
+        };
+
+        let mut palette = NbtList::new();
+        palette.push(&BlockState::new("air", None));
+        palette.push(&BlockState::new("stone", None));
+
+        assert!(matches!(
+            Region::new_from_nbt(&region_nbt(NbtList::new(), Vector3::new(4, 4, 4))),
+            Err(RegionParseError::EmptyPalette)
+        ));
+        assert!(matches!(
+            Region::new_from_nbt(&region_nbt(palette.clone(), Vector3::new(4, 0, 4))),
+            Err(RegionParseError::InvalidDimensions(_))
+        ));
+        assert!(Region::new_from_nbt(&region_nbt(palette.clone(), Vector3::new(4, 4, 4))).is_ok());
+
+        let (empty, _) = Region::new().to_nbt().unwrap();
+
+        assert!(Region::new_from_nbt(&empty).unwrap().is_empty());
+
+        assert!(matches!(
+            Region::new_from_nbt(&region_nbt(palette.clone(), Vector3::new(4, 4, 5))),
+            Err(RegionParseError::BlockStatesLengthMismatch {
+                expected: 3,
+                found: 2
+            })
+        ));
+
+        palette.push(&BlockState::new("dirt", None));
+
+        // Three block states still take 2 bits each, which leaves room for an index of 3
+        let mut out_of_range = region_nbt(palette.clone(), Vector3::new(4, 4, 4));
+        out_of_range.insert("BlockStates", vec![0b11_i64, 0]);
+
+        assert!(matches!(
+            Region::new_from_nbt(&out_of_range),
+            Err(RegionParseError::MalformedBlockData)
+        ));
+
+        // Sizes whose volume or corners don't fit in an i32
+        for size in [
+            Vector3::new(2000, 2000, 2000),
+            Vector3::new(-2000, 2000, -2000),
+            Vector3::new(i32::MIN, 1, 1),
+        ] {
+            assert!(matches!(
+                Region::new_from_nbt(&region_nbt(palette.clone(), size)),
+                Err(RegionParseError::InvalidDimensions(_))
+            ));
+        }
+
+        let mut far_away = region_nbt(palette, Vector3::new(4, 4, 4));
+        far_away.insert("Position", Vector3::new(i32::MAX, 0, 0));
+
+        assert!(matches!(
+            Region::new_from_nbt(&far_away),
+            Err(RegionParseError::InvalidDimensions(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_structure_nbt() {
+        let mut region = Region::new();
+
+        region.volume = Volume::new(Vector3::new(5, 5, 5), Vector3::new(1, 1, 1));
+        region.set_block(Vector3::new(0, 0, 0), "chest");
+        region.set_block(Vector3::new(-1, 2, 0), "stone");
+
+        let mut chest = NbtCompound::new();
+
+        chest.insert("x", 0);
+        chest.insert("y", 0);
+        chest.insert("z", 0);
+        chest.insert("id", "minecraft:chest");
+
+        let mut tile_entities = NbtList::new();
+        tile_entities.push(chest);
+
+        region.tile_entities = Some(tile_entities);
+
+        let structure = region.to_structure_nbt();
+
+        assert_eq!(
+            Vector3::from_nbt_list(structure.get::<_, &NbtList>("size").unwrap()),
+            Some(Vector3::new(2, 3, 1))
+        );
+
+        let palette =
+            Region::parse_palette(structure.get::<_, &NbtList>("palette").unwrap()).unwrap();
+        let blocks = structure.get::<_, &NbtList>("blocks").unwrap();
+
+        assert_eq!(blocks.len(), 2);
+
+        for block in blocks {
+            let block = if let NbtTag::Compound(block) = block {
+                block
+            } else {
+                panic!("block isn't a compound")
+            };
+
+            let pos = Vector3::from_nbt_list(block.get::<_, &NbtList>("pos").unwrap()).unwrap();
+            let state = &palette[block.get::<_, i32>("state").unwrap() as usize];
+
+            if pos == Vector3::new(1, 0, 0) {
+                assert_eq!(state, &BlockState::new("chest", None));
+
+                let nbt = block.get::<_, &NbtCompound>("nbt").unwrap();
+
+                assert_eq!(nbt.get::<_, &str>("id").unwrap(), "minecraft:chest");
+                assert!(!nbt.inner().contains_key("x"));
+            } else {
+                assert_eq!(pos, Vector3::new(0, 2, 0));
+                assert_eq!(state, &BlockState::new("stone", None));
+                assert!(!block.inner().contains_key("nbt"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_structure_nbt() {
+        let mut region = Region::new();
+
+        region.volume = Volume::new(Vector3::new(5, 5, 5), Vector3::new(1, 1, 1));
+        region.set_block(Vector3::new(0, 0, 0), "chest");
+        region.set_block(Vector3::new(-1, 2, 0), "stone");
+        region.set_block(
+            Vector3::new(-1, 0, 0),
+            "observer[facing=west]".parse::<BlockState>().unwrap(),
+        );
+
+        let mut chest = NbtCompound::new();
+
+        chest.insert("x", 0);
+        chest.insert("y", 0);
+        chest.insert("z", 0);
+        chest.insert("id", "minecraft:chest");
+
+        let mut tile_entities = NbtList::new();
+        tile_entities.push(chest);
+
+        region.tile_entities = Some(tile_entities);
+
+        let parsed = Region::from_structure_nbt(&region.to_structure_nbt()).unwrap();
+
+        assert_eq!(
+            parsed.volume,
+            Volume::new(Vector3::new(0, 0, 0), Vector3::new(2, 3, 1))
+        );
+        assert_eq!(parsed.blocks().len(), 3);
+        assert_eq!(parsed.blocks()[&Vector3::new(1, 0, 0)], "chest");
+        assert_eq!(parsed.blocks()[&Vector3::new(0, 2, 0)], "stone");
+        assert_eq!(
+            parsed.blocks()[&Vector3::new(0, 0, 0)],
+            "observer[facing=west]".parse::<BlockState>().unwrap()
+        );
+
+        let tile_entities = parsed.tile_entities.unwrap();
+
+        assert_eq!(tile_entities.len(), 1);
+
+        if let NbtTag::Compound(chest) = &tile_entities[0] {
+            assert_eq!(Vector3::from_xyz_nbt(chest).unwrap(), Vector3::new(1, 0, 0));
+            assert_eq!(chest.get::<_, &str>("id").unwrap(), "minecraft:chest");
+        } else {
+            panic!("tile entity isn't a compound");
+        }
+
+        let mut bad_state = NbtCompound::new();
+
+        bad_state.insert("size", Vector3::new(1, 1, 1).to_nbt_list());
+        bad_state.insert("palette", NbtList::new());
+
+        let mut block = NbtCompound::new();
+
+        block.insert("pos", Vector3::new(0, 0, 0).to_nbt_list());
+        block.insert("state", 0);
+
+        let mut blocks = NbtList::new();
+        blocks.push(block);
+
+        bad_state.insert("blocks", blocks);
+
+        assert!(matches!(
+            Region::from_structure_nbt(&bad_state),
+            Err(RegionParseError::MalformedBlockData)
+        ));
+    }
+
+    #[test]
+    fn test_entities() {
+        let mut region = Region::new();
+
+        assert!(region.entities().is_empty());
+
+        let mut pig = Entity::new("minecraft:pig", Vector3::new(0.5, 1.0, 0.5));
+        pig.nbt.insert("Saddle", 1_i8);
+
+        region.add_entity(pig.clone());
+        region.add_entity(Entity::new("minecraft:cow", Vector3::new(2.5, 1.0, 0.5)));
+
+        region.entities.as_mut().unwrap().push(NbtCompound::new());
+
+        let entities = region.entities();
+
+        assert_eq!(entities.len(), 2);
+        assert_eq!(entities[0], pig);
+        assert_eq!(entities[1].id, "minecraft:cow");
+    }
+
+    #[test]
+    fn test_tile_entities() {
+        let mut region = Region::new();
+
+        assert_eq!(region.tile_entity_at(Vector3::new(0, 0, 0)), None);
+
+        let mut sign = NbtCompound::new();
+        sign.insert("id", "minecraft:sign");
+        sign.insert("Text1", "bruh");
+
+        let mut chest = NbtCompound::new();
+        chest.insert("id", "minecraft:chest");
+
+        region.set_tile_entity(Vector3::new(1, 2, 3), sign);
+        region.set_tile_entity(Vector3::new(0, 0, 0), chest);
+
+        let sign = region.tile_entity_at(Vector3::new(1, 2, 3)).unwrap();
+
+        assert_eq!(sign.get::<_, &str>("Text1").unwrap(), "bruh");
+        assert_eq!(sign.get::<_, i32>("z").unwrap(), 3);
+
+        let mut new_sign = NbtCompound::new();
+        new_sign.insert("id", "minecraft:sign");
+        new_sign.insert("Text1", "yeet");
+
+        region.set_tile_entity(Vector3::new(1, 2, 3), new_sign);
+
+        assert_eq!(region.tile_entities.as_ref().unwrap().len(), 2);
+        assert_eq!(
+            region
+                .tile_entity_at(Vector3::new(1, 2, 3))
+                .unwrap()
+                .get::<_, &str>("Text1")
+                .unwrap(),
+            "yeet"
+        );
+        assert_eq!(
+            region
+                .tile_entity_at(Vector3::new(0, 0, 0))
+                .unwrap()
+                .get::<_, &str>("id")
+                .unwrap(),
+            "minecraft:chest"
+        );
+        assert_eq!(region.tile_entity_at(Vector3::new(3, 2, 1)), None);
+    }
+
+    #[test]
+    fn test_from_blocks() {
+        let mut blocks = HashMap::new();
+
+        blocks.insert(Vector3::new(0, 0, 0), BlockState::new("stone", None));
+        blocks.insert(Vector3::new(1, 0, 0), BlockState::new("air", None));
+        blocks.insert(Vector3::new(1, 3, 0), BlockState::new("dirt", None));
+        blocks.insert(Vector3::new(5, 5, 5), BlockState::new("air", None));
+
+        let region = Region::from_blocks(Vector3::new(-4, 2, 0), blocks);
+
+        assert_eq!(region.blocks().len(), 2);
+        assert!(!region.blocks().contains_key(&Vector3::new(1, 0, 0)));
+        assert!(!region.blocks().contains_key(&Vector3::new(5, 5, 5)));
+        assert_eq!(region.blocks()[&Vector3::new(1, 3, 0)], "dirt");
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(-4, 2, 0), Vector3::new(2, 4, 1))
+        );
+
+        let empty = Region::from_blocks(Vector3::new(1, 2, 3), HashMap::new());
+
+        assert!(empty.is_empty());
+        assert_eq!(empty.volume().origin(), Vector3::new(1, 2, 3));
+    }
+
+    #[test]
+    fn test_offset_entities() {
+        let mut region = Region::new();
+
+        region.add_entity(Entity::new("minecraft:pig", Vector3::new(0.5, 0.0, 0.5)));
+
+        let mut item_frame = Entity::new("minecraft:item_frame", Vector3::new(1.5, 1.5, 0.0));
+        item_frame.nbt.insert("TileX", 1);
+        item_frame.nbt.insert("TileY", 1);
+        item_frame.nbt.insert("TileZ", 0);
+
+        region.add_entity(item_frame);
+
+        let mut chest = NbtCompound::new();
+        chest.insert("id", "minecraft:chest");
+
+        region.set_tile_entity(Vector3::new(1, 2, 3), chest);
+
+        let mut tick = NbtCompound::new();
+        tick.insert("Block", "minecraft:repeater");
+        tick.insert("x", 4);
+        tick.insert("y", 0);
+        tick.insert("z", 0);
+
+        let mut ticks = NbtList::new();
+        ticks.push(tick);
+        region.pending_block_ticks = Some(ticks);
+
+        region.offset_entities(Vector3::new(10, -1, 2));
+
+        let entities = region.entities();
+
+        assert_eq!(entities[0].position, Vector3::new(10.5, -1.0, 2.5));
+        assert_eq!(entities[1].position, Vector3::new(11.5, 0.5, 2.0));
+        assert_eq!(entities[1].nbt.get::<_, i32>("TileX").unwrap(), 11);
+        assert_eq!(entities[1].nbt.get::<_, i32>("TileZ").unwrap(), 2);
+
+        assert!(region.tile_entity_at(Vector3::new(1, 2, 3)).is_none());
+        assert_eq!(
+            region
+                .tile_entity_at(Vector3::new(11, 1, 5))
+                .unwrap()
+                .get::<_, &str>("id")
+                .unwrap(),
+            "minecraft:chest"
+        );
+
+        let tick = match region.pending_block_ticks.as_ref().unwrap().iter().next() {
+            Some(NbtTag::Compound(tick)) => tick.clone(),
+            _ => panic!("The pending tick should still be a compound"),
+        };
+
+        assert_eq!(
+            Vector3::<i32>::from_xyz_nbt(&tick).unwrap(),
+            Vector3::new(14, -1, 2)
+        );
+        assert_eq!(region.pending_fluid_ticks, None);
+    }
+
+    #[test]
+    fn test_set_volume() {
+        let mut region = Region::new();
+
+        region.volume = Volume::new(Vector3::new(10, 0, 10), Vector3::new(2, 2, 2));
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 1, 1), "dirt");
+        region.set_tile_entity(Vector3::new(1, 1, 1), NbtCompound::new());
+
+        let world_blocks: HashSet<_> = region
+            .iter()
+            .map(|(pos, block)| (pos, block.clone()))
+            .collect();
+
+        region.set_volume(Volume::new(Vector3::new(8, 0, 8), Vector3::new(6, 4, 6)));
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(8, 0, 8), Vector3::new(6, 4, 6))
+        );
+        assert_eq!(
+            region
+                .iter()
+                .map(|(pos, block)| (pos, block.clone()))
+                .collect::<HashSet<_>>(),
+            world_blocks
+        );
+        assert_eq!(region.blocks()[&Vector3::new(3, 1, 3)], "dirt");
+        assert!(region.tile_entity_at(Vector3::new(3, 1, 3)).is_some());
+
+        let (nbt, _) = region.to_nbt().unwrap();
+
+        assert_eq!(
+            Vector3::from_nbt(&nbt, "Size").unwrap(),
+            Vector3::new(6, 4, 6)
+        );
+        assert_eq!(
+            Vector3::from_nbt(&nbt, "Position").unwrap(),
+            Vector3::new(8, 0, 8)
+        );
+
+        region.set_volume(Volume::new(Vector3::new(8, 0, 8), Vector3::new(1, 1, 1)));
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(8, 0, 8), Vector3::new(4, 2, 4))
+        );
+    }
+
+    #[test]
+    fn test_iter_dense() {
+        let mut region = Region::new();
+
+        region.volume = Volume::new(Vector3::new(10, 0, 0), Vector3::new(2, 2, 1));
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 1, 0), "dirt");
+
+        let blocks: Vec<_> = region
+            .iter_dense()
+            .map(|(pos, block)| (pos, block.get_block().as_str()))
+            .collect();
+
+        assert_eq!(
+            blocks,
+            vec![
+                (Vector3::new(10, 0, 0), "minecraft:stone"),
+                (Vector3::new(11, 0, 0), "minecraft:air"),
+                (Vector3::new(10, 1, 0), "minecraft:air"),
+                (Vector3::new(11, 1, 0), "minecraft:dirt"),
+            ]
+        );
+
+        assert_eq!(Region::new().iter_dense().count(), 0);
+    }
+
+    #[test]
+    fn test_set_blocks_bulk() {
+        let mut region = Region::new();
+        region.set_block(Vector3::new(-1, 0, 0), "dirt");
+        region.set_block(Vector3::new(0, 0, 0), "dirt");
+
+        region.set_blocks_bulk((0..10_000).map(|i| {
+            (
+                Vector3::new(i % 100, i / 100, 0),
+                BlockState::new(if i % 2 == 0 { "stone" } else { "granite" }, None),
+            )
+        }));
+
+        assert_eq!(region.blocks().len(), 10_001);
+        assert_eq!(
+            region[Vector3::new(0, 0, 0)],
+            BlockState::new("stone", None)
+        );
+        assert_eq!(
+            region[Vector3::new(1, 0, 0)],
+            BlockState::new("granite", None)
+        );
+        assert_eq!(
+            region[Vector3::new(-1, 0, 0)],
+            BlockState::new("dirt", None)
+        );
+        assert_eq!(region.dimensions(), Vector3::new(101, 100, 1));
+
+        region.set_blocks_bulk(vec![
+            (Vector3::new(-1, 0, 0), BlockState::new("air", None)),
+            (Vector3::new(50, 200, 0), BlockState::new("stone", None)),
+        ]);
+
+        assert_eq!(region.blocks().len(), 10_001);
+        assert_eq!(region.dimensions(), Vector3::new(100, 201, 1));
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut old = Region::new();
+        old.set_block(Vector3::new(0, 0, 0), "stone");
+        old.set_block(Vector3::new(1, 0, 0), "dirt");
+        old.set_block(Vector3::new(0, 1, 0), "glass");
+        old.set_block(Vector3::new(3, 0, 0), "granite");
+
+        assert!(old.diff(&old).is_empty());
+
+        // The new region has its origin moved, so its blocks have different relative positions
+        let mut new = Region::new();
+        new.volume = Volume::new(Vector3::new(1, 0, 0), Vector3::new(0, 0, 0));
+        new.set_block(Vector3::new(-1, 0, 0), "stone");
+        new.set_block(Vector3::new(0, 0, 0), "coarse_dirt");
+        new.set_block(Vector3::new(1, 0, 0), "sand");
+        new.set_block(Vector3::new(0, 2, 0), "sand");
+        new.set_block(Vector3::new(2, 0, 0), "granite");
+
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.added,
+            vec![
+                (Vector3::new(2, 0, 0), BlockState::new("sand", None)),
+                (Vector3::new(1, 2, 0), BlockState::new("sand", None)),
+            ]
+        );
+        assert_eq!(
+            diff.removed,
+            vec![(Vector3::new(0, 1, 0), BlockState::new("glass", None))]
+        );
+        assert_eq!(
+            diff.changed,
+            vec![(
+                Vector3::new(1, 0, 0),
+                BlockState::new("dirt", None),
+                BlockState::new("coarse_dirt", None)
+            )]
+        );
+
+        let reverse = new.diff(&old);
+
+        assert_eq!(reverse.added, diff.removed);
+        assert_eq!(reverse.removed, diff.added);
+        assert_eq!(reverse.changed.len(), 1);
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut a = Region::new();
+        a.volume = Volume::new(Vector3::new(-2, 0, 0), Vector3::new(4, 4, 4));
+        a.set_block(Vector3::new(0, 0, 0), "stone");
+        a.set_block(Vector3::new(1, 0, 0), "dirt");
+        a.set_block(Vector3::new(2, 3, 1), "glass");
+
+        let mut b = Region::new();
+        b.volume = a.volume;
+        b.set_block(Vector3::new(0, 0, 0), "stone");
+        b.set_block(Vector3::new(1, 0, 0), "grass_block");
+        b.set_block(Vector3::new(3, 1, 2), "sand");
+        b.set_block(Vector3::new(9, 0, 0), "granite");
+
+        let original = a.clone();
+        let diff = a.diff(&b);
+
+        a.apply(&diff);
+
+        assert!(a == b);
+        assert!(a.diff(&b).is_empty());
+        assert_eq!(a.volume(), b.volume());
+        assert_eq!(a.bounds_of(|_| true), b.bounds_of(|_| true));
+
+        a.apply(&b.diff(&original));
+
+        assert!(a == original);
+
+        let mut hand_made = RegionDiff::default();
+        hand_made
+            .added
+            .push((Vector3::new(-2, 0, 0), BlockState::new("air", None)));
+        hand_made.changed.push((
+            Vector3::new(-1, 0, 0),
+            BlockState::new("dirt", None),
+            BlockState::new("air", None),
+        ));
+
+        a.apply(&hand_made);
+
+        assert_eq!(a.blocks().len(), 1);
+        assert_eq!(a[Vector3::new(2, 3, 1)], BlockState::new("glass", None));
+    }
+
+    #[test]
+    fn test_world_volume() {
+        let mut region = Region::new();
+        region.volume = Volume::new(Vector3::new(5, 5, 5), Vector3::new(-4, 2, 2));
+
+        region.set_block(Vector3::new(-3, 0, 0), "stone");
+        region.set_block(Vector3::new(-1, 4, 1), "stone");
+
+        let world_volume = region.world_volume();
+
+        assert_eq!(
+            world_volume,
+            Volume::new(Vector3::new(2, 5, 5), Vector3::new(3, 5, 2))
+        );
+        assert_eq!(
+            region.volume().union(world_volume),
+            region.volume().make_size_positive()
+        );
+
+        for (pos, _) in region.blocks() {
+            assert!(!world_volume.contains(*pos));
+        }
+
+        for (pos, _) in region.iter() {
+            assert!(world_volume.contains(pos));
+        }
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut region = Region::new();
+        region.volume = Volume::new(Vector3::new(5, 0, 0), Vector3::new(6, 4, 4));
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(5, 3, 3), "stone");
+        region.set_block(Vector3::new(2, 1, 1), "dirt");
+        region.set_block(Vector3::new(3, 2, 1), "granite");
+        region.add_entity(Entity::new("minecraft:pig", Vector3::new(7.5, 1.0, 1.5)));
+
+        region.shrink_to_fit();
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(5, 0, 0), Vector3::new(6, 4, 4))
+        );
+
+        region.remove_block(Vector3::new(0, 0, 0));
+        region.remove_block(Vector3::new(5, 3, 3));
+
+        assert_eq!(region.volume().volume(), 96);
+
+        let world_blocks: HashSet<_> = region.iter().map(|(pos, _)| pos).collect();
+
+        region.shrink_to_fit();
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(7, 1, 1), Vector3::new(2, 2, 1))
+        );
+        assert_eq!(region.volume, region.volume());
+        assert_eq!(
+            region.iter().map(|(pos, _)| pos).collect::<HashSet<_>>(),
+            world_blocks
+        );
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "dirt");
+        assert_eq!(region.entities()[0].position, Vector3::new(5.5, 0.0, 0.5));
+
+        region.clear();
+        region.volume = Volume::new(Vector3::new(3, 3, 3), Vector3::new(2, 2, 2));
+        region.shrink_to_fit();
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(3, 3, 3), Vector3::new(0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut schematic = crate::Schematic::new(None, None, None, None);
+
+        let mut region = Region::new();
+        region.volume = Volume::new(Vector3::new(5, 5, 5), Vector3::new(2, 2, 2));
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(3, 1, 0), "dirt");
+        region.set_tile_entity(Vector3::new(0, 0, 0), NbtCompound::new());
+        region.add_entity(Entity::new("minecraft:pig", Vector3::new(0.5, 0.0, 0.5)));
+        region.extra.insert("ModData", 1);
+
+        schematic.regions.insert("main".to_string(), region);
+
+        let region = schematic.get_region_mut("main").unwrap();
+        region.clear();
+
+        assert_eq!(schematic.total_blocks(), 0);
+
+        let region = &schematic.regions["main"];
+
+        assert!(region.is_empty());
+        assert!(region.palette().is_empty());
+        assert!(region.entities().is_empty());
+        assert_eq!(region.tile_entity_at(Vector3::new(0, 0, 0)), None);
+        assert_eq!(region.volume(), Volume::default());
+        assert_eq!(region.extra.get::<_, i32>("ModData").unwrap(), 1);
+
+        let mut cleared = region.clone();
+        cleared.extra = NbtCompound::new();
+
+        assert!(cleared == Region::new());
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 0, 0), "dirt");
+        region.add_entity(Entity::new("minecraft:pig", Vector3::new(0.5, 1.0, 0.5)));
+
+        let mut clone = region.clone();
+
+        clone.set_block(Vector3::new(0, 0, 0), "basalt");
+        clone.set_block(Vector3::new(1, 0, 0), "air");
+        clone.set_block(Vector3::new(2, 0, 0), "granite");
+        clone.add_entity(Entity::new("minecraft:cow", Vector3::new(0.5, 1.0, 0.5)));
+        clone.volume = Volume::new(Vector3::new(5, 5, 5), Vector3::new(1, 1, 1));
+
+        assert_eq!(region.blocks().len(), 2);
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "stone");
+        assert_eq!(region.blocks()[&Vector3::new(1, 0, 0)], "dirt");
+        assert_eq!(region.entities().len(), 1);
+        assert_eq!(region.volume, Volume::default());
+
+        assert_eq!(clone.blocks().len(), 2);
+        assert_eq!(clone.entities().len(), 2);
+    }
+
+    #[test]
+    fn test_eq() {
+        let mut region1 = Region::new();
+        region1.set_block(Vector3::new(0, 0, 0), "stone");
+        region1.set_block(Vector3::new(2, 1, 0), "dirt");
+
+        let mut region2 = Region::new();
+        region2.volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(3, 2, 1));
+        region2.set_block(Vector3::new(2, 1, 0), "dirt");
+        region2.set_block(Vector3::new(0, 0, 0), "stone");
+
+        assert!(region1 == region2);
+
+        let (nbt, _) = region1.to_nbt().unwrap();
+
+        assert!(Region::new_from_nbt(&nbt).unwrap() == region1);
+
+        region2.volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(4, 2, 1));
+
+        assert!(region1 != region2);
+
+        let mut region3 = region1.clone();
+        region3.volume = Volume::new(Vector3::new(1, 0, 0), Vector3::new(1, 1, 1));
+
+        assert!(region1 != region3);
+
+        let mut region4 = region1.clone();
+        region4.add_entity(Entity::new("minecraft:pig", Vector3::new(0.5, 1.0, 0.5)));
+
+        assert!(region1 != region4);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut region = Region::new();
+
+        region.set_block(
+            Vector3::new(1, 2, 3),
+            "observer[facing=west]".parse::<BlockState>().unwrap(),
+        );
+
+        assert_eq!(region[Vector3::new(1, 2, 3)].properties["facing"], "west");
+        assert_eq!(region[Vector3::new(0, 0, 0)], BlockState::new("air", None));
+        assert!(region[Vector3::new(0, 0, 0)].properties.is_empty());
+
+        let non_air = region
+            .volume()
+            .into_iter()
+            .filter(|pos| region[*pos] != "air")
+            .count();
+
+        assert_eq!(non_air, 1);
+    }
+
+    #[test]
+    fn test_volume_large_region() {
+        let mut region = Region::new();
+        region.volume = Volume::new(Vector3::new(10, 0, 10), Vector3::new(1, 1, 1));
+
+        for pos in Volume::new(Vector3::new(0, 0, 0), Vector3::new(100, 20, 100)) {
+            region.set_block(pos, "stone");
+        }
+
+        let expected = Volume::new(Vector3::new(10, 0, 10), Vector3::new(100, 20, 100));
+
+        // Going through all 200000 blocks on every call would make this take minutes
+        for _ in 0..10000 {
+            assert_eq!(region.volume(), expected);
+        }
+
+        region.set_block(Vector3::new(50, 10, 50), "dirt");
+        region.set_block(Vector3::new(-1, 0, 0), "dirt");
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(9, 0, 10), Vector3::new(101, 20, 100))
+        );
+
+        region.set_block(Vector3::new(-1, 0, 0), "air");
+
+        for pos in Volume::new(Vector3::new(0, 19, 0), Vector3::new(100, 1, 100)) {
+            region.set_block(pos, "air");
+        }
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(10, 0, 10), Vector3::new(100, 19, 100))
+        );
+    }
+
+    #[test]
+    fn test_palette_storage() {
+        let mut region = Region::new();
+
+        for pos in Volume::new(Vector3::new(0, 0, 0), Vector3::new(10, 10, 10)) {
+            region.set_block(pos, "stone");
+        }
+
+        region.set_block(Vector3::new(0, 0, 0), "dirt");
+        region.set_block(Vector3::new(1, 0, 0), "air");
+
+        assert_eq!(region.palette.len(), 2);
+        assert_eq!(region.blocks().len(), 999);
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "dirt");
+        assert_eq!(region.blocks().get(&Vector3::new(1, 0, 0)), None);
+        assert_eq!(
+            region.blocks().values().filter(|v| *v == "stone").count(),
+            998
+        );
+
+        let (nbt, _) = region.to_nbt().unwrap();
+        let parsed = Region::new_from_nbt(&nbt).unwrap();
+
+        assert!(parsed == region);
+        assert_eq!(parsed.blocks()[&Vector3::new(9, 9, 9)], "stone");
+    }
+
+    #[test]
+    fn test_to_nbt_many_block_states() {
+        let mut region = Region::new();
+
+        let states: Vec<_> = (0..1024)
+            .map(|i| {
+                let mut properties = HashMap::new();
+                properties.insert("note".to_string(), i.to_string());
+
+                BlockState::new("note_block", Some(properties))
+            })
+            .collect();
+
+        // Looking up each block's palette index by scanning the palette would take around 67 million comparisons here
+        for (i, pos) in Volume::new(Vector3::new(0, 0, 0), Vector3::new(64, 16, 64))
+            .into_iter()
+            .enumerate()
+        {
+            region.set_block(pos, states[i % states.len()].clone());
+        }
+
+        let (nbt, _) = region.to_nbt().unwrap();
+
+        assert_eq!(
+            nbt.get::<_, &NbtList>("BlockStatePalette").unwrap().len(),
+            1025
+        );
+
+        let parsed = Region::new_from_nbt(&nbt).unwrap();
+
+        assert!(parsed == region);
+    }
+
+    #[test]
+    fn test_bounds_of() {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(20, 5, 3), "stone");
+        region.set_block(Vector3::new(4, 1, -2), "redstone_wire");
+        region.set_block(Vector3::new(-3, 2, 6), "redstone_wire");
+        region.set_block(
+            Vector3::new(1, 8, 1),
+            "repeater[facing=east]".parse::<BlockState>().unwrap(),
+        );
+
+        let is_redstone = |block: &BlockState| *block == "redstone_wire" || *block == "repeater";
+
+        assert_eq!(
+            region.bounds_of(is_redstone),
+            Some(Volume::new(Vector3::new(-3, 1, -2), Vector3::new(8, 8, 9)))
+        );
+        assert_eq!(
+            region.bounds_of(|block| *block == "stone"),
+            Some(Volume::new(Vector3::new(0, 0, 0), Vector3::new(21, 6, 4)))
+        );
+        assert_eq!(
+            region.bounds_of(|block| *block == "repeater"),
+            Some(Volume::new(Vector3::new(1, 8, 1), Vector3::new(1, 1, 1)))
+        );
+        assert_eq!(region.bounds_of(|block| *block == "dirt"), None);
+    }
+
+    #[test]
+    fn test_count_of() {
+        let mut region = Region::new();
+
+        let mut properties = HashMap::new();
+        properties.insert("power".to_string(), "15".to_string());
+
+        region.set_block(Vector3::new(0, 0, 0), "redstone_wire");
+        region.set_block(Vector3::new(1, 0, 0), "redstone_wire");
+        region.set_block(
+            Vector3::new(2, 0, 0),
+            BlockState::new("redstone_wire", Some(properties)),
+        );
+        region.set_block(Vector3::new(3, 0, 0), "stone");
+
+        assert_eq!(region.count_of(&BlockState::new("redstone_wire", None)), 2);
+        assert_eq!(region.count_of(&BlockState::new("stone", None)), 1);
+        assert_eq!(region.count_of(&BlockState::new("dirt", None)), 0);
+
+        assert_eq!(region.count_of_block("redstone_wire"), 3);
+        assert_eq!(region.count_of_block("minecraft:stone"), 1);
+        assert_eq!(region.count_of_block("air"), 0);
+    }
+
+    #[test]
+    fn test_fill_pyramid() {
+        let mut region = Region::new();
+
+        region.fill_pyramid(Vector3::new(0, 0, 0), 5, BlockState::new("sandstone", None));
+
+        let layer_size = |y: i32| region.blocks().keys().filter(|pos| pos.y == y).count();
+
+        assert_eq!(layer_size(0), 25);
+        assert_eq!(layer_size(1), 9);
+        assert_eq!(layer_size(2), 1);
+        assert_eq!(layer_size(3), 0);
+        assert_eq!(region.blocks().len(), 35);
+
+        assert!(region.blocks().contains_key(&Vector3::new(-2, 0, -2)));
+        assert!(region.blocks().contains_key(&Vector3::new(2, 0, 2)));
+        assert!(region.blocks().contains_key(&Vector3::new(0, 2, 0)));
+        assert!(!region.blocks().contains_key(&Vector3::new(2, 1, 2)));
+    }
+
+    #[test]
+    fn test_mirror_copy() {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 0, 2), "dirt");
+        region.set_block(
+            Vector3::new(2, 1, 0),
+            "observer[facing=east]".parse::<BlockState>().unwrap(),
+        );
+
+        region.mirror_copy(Axis::X, 3);
+
+        assert_eq!(region.blocks().len(), 6);
+
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "stone");
+        assert_eq!(region.blocks()[&Vector3::new(1, 0, 2)], "dirt");
+        assert_eq!(
+            region.blocks()[&Vector3::new(2, 1, 0)].properties["facing"],
+            "east"
+        );
+
+        assert_eq!(region.blocks()[&Vector3::new(6, 0, 0)], "stone");
+        assert_eq!(region.blocks()[&Vector3::new(5, 0, 2)], "dirt");
+        assert_eq!(
+            region.blocks()[&Vector3::new(4, 1, 0)].properties["facing"],
+            "west"
+        );
+
+        region.mirror_copy(Axis::Z, 0);
+
+        assert_eq!(region.blocks().len(), 8);
+        assert_eq!(region.blocks()[&Vector3::new(1, 0, -2)], "dirt");
+        assert_eq!(region.blocks()[&Vector3::new(5, 0, -2)], "dirt");
+
+        // Blocks on the plane aren't replaced by their own reflection
+        region.mirror_copy(Axis::X, 2);
+
+        assert_eq!(
+            region.blocks()[&Vector3::new(2, 1, 0)].properties["facing"],
+            "east"
+        );
+        assert_eq!(
+            region.blocks()[&Vector3::new(0, 1, 0)].properties["facing"],
+            "east"
+        );
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mut region = Region::new();
+        region.volume = Volume::new(Vector3::new(10, 0, 0), Vector3::new(4, 2, 2));
+
+        let stairs = BlockState::from_parts(
+            "oak_stairs",
+            &[
+                ("facing", "east"),
+                ("half", "bottom"),
+                ("shape", "inner_left"),
+            ],
+        );
+
+        region.set_block(Vector3::new(0, 0, 0), stairs.clone());
+        region.set_block(Vector3::new(1, 1, 1), "stone");
+
+        region.mirror(Axis::X);
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(10, 0, 0), Vector3::new(4, 2, 2))
+        );
+        assert_eq!(region.blocks().len(), 2);
+        assert_eq!(
+            region.blocks()[&Vector3::new(3, 0, 0)],
+            BlockState::from_parts(
+                "oak_stairs",
+                &[
+                    ("facing", "west"),
+                    ("half", "bottom"),
+                    ("shape", "inner_right")
+                ]
+            )
+        );
+        assert_eq!(region.blocks()[&Vector3::new(2, 1, 1)], "stone");
+
+        region.mirror(Axis::X);
+
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], stairs);
+
+        region.mirror(Axis::Y);
+
+        assert_eq!(
+            region.blocks()[&Vector3::new(0, 1, 0)].properties["half"],
+            "top"
+        );
+        assert_eq!(region.blocks()[&Vector3::new(1, 0, 1)], "stone");
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut region = Region::new();
+        region.volume = Volume::new(Vector3::new(5, 0, 5), Vector3::new(3, 1, 2));
+
+        region.set_block(
+            Vector3::new(0, 0, 0),
+            BlockState::from_parts("rail", &[("shape", "north_east")]),
+        );
+        region.set_block(Vector3::new(2, 0, 1), "stone");
+
+        let original = region.clone();
+
+        region.rotate(1);
+
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(5, 0, 5), Vector3::new(2, 1, 3))
+        );
+        assert_eq!(
+            region.blocks()[&Vector3::new(1, 0, 0)].properties["shape"],
+            "south_east"
+        );
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 2)], "stone");
+
+        region.rotate(3);
+
+        assert!(region == original);
+
+        region.rotate(-2);
+        region.rotate(2);
+
+        assert!(region == original);
+    }
+
+    #[test]
+    fn test_to_setblock_commands() {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(1, 0, 0), "stone");
+        region.set_block(
+            Vector3::new(0, 1, 0),
+            "observer[powered=false,facing=west]"
+                .parse::<BlockState>()
+                .unwrap(),
+        );
+
+        assert_eq!(
+            region.to_setblock_commands(Vector3::new(100, 64, -20)),
+            vec![
+                "/setblock 101 64 -20 minecraft:stone",
+                "/setblock 100 65 -20 minecraft:observer[facing=west,powered=false]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_setblock_commands() {
+        let region = Region::from_setblock_commands(&[
+            "/setblock 1 2 3 minecraft:observer[facing=west,powered=false]",
+            "",
+            "fill 0 0 0 1 -1 1 stone",
+            "/setblock 0 0 0 air",
+            "/fill 0 0 0 1 0 0 dirt keep",
+        ])
+        .unwrap();
+
+        assert_eq!(region.blocks().len(), 9);
+        assert_eq!(
+            region.blocks()[&Vector3::new(1, 2, 3)],
+            "observer[facing=west,powered=false]"
+                .parse::<BlockState>()
+                .unwrap()
+        );
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "dirt");
+        assert_eq!(region.blocks()[&Vector3::new(1, 0, 0)], "stone");
+        assert_eq!(region.blocks()[&Vector3::new(1, -1, 1)], "stone");
+        assert_eq!(region.count_of_block("stone"), 7);
+
+        assert!(Region::from_setblock_commands(&["/setblock ~ ~1 ~ stone"]).is_err());
+        assert!(Region::from_setblock_commands(&["/setblock 0 0 stone"]).is_err());
+        assert!(Region::from_setblock_commands(&["/fill 0 0 0 1 1 1 stone hollow"]).is_err());
+        assert!(Region::from_setblock_commands(&["/give @p stone"]).is_err());
+        assert!(Region::from_setblock_commands(&["/setblock 0 0 0 stone[facing]"]).is_err());
+    }
+
+    fn merge_test_regions() -> (Region, Region) {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 0, 0), "stone");
+        region.set_block(Vector3::new(5, 0, 0), "stone");
+
+        let mut other = Region::new();
+
+        other.volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(3, 1, 1));
+        other.set_block(Vector3::new(0, 0, 0), "dirt");
+        other.set_block(Vector3::new(2, 0, 0), "dirt");
+
+        (region, other)
+    }
+
+    #[test]
+    fn test_merge_with_overwrite() {
+        let (mut region, other) = merge_test_regions();
+
+        region.merge_with(&other, Vector3::new(1, 0, 0), MergeStrategy::Overwrite);
+
+        assert_eq!(region.blocks().len(), 4);
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "stone");
+        assert_eq!(region.blocks()[&Vector3::new(1, 0, 0)], "dirt");
+        assert_eq!(region.blocks().get(&Vector3::new(2, 0, 0)), None);
+        assert_eq!(region.blocks()[&Vector3::new(3, 0, 0)], "dirt");
+        assert_eq!(region.blocks()[&Vector3::new(5, 0, 0)], "stone");
+    }
+
+    #[test]
+    fn test_merge_with_keep_existing() {
+        let (mut region, other) = merge_test_regions();
+
+        region.merge_with(&other, Vector3::new(1, 0, 0), MergeStrategy::KeepExisting);
+
+        assert_eq!(region.blocks().len(), 4);
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "stone");
+        assert_eq!(region.blocks()[&Vector3::new(1, 0, 0)], "stone");
+        assert_eq!(region.blocks()[&Vector3::new(3, 0, 0)], "dirt");
+        assert_eq!(region.blocks()[&Vector3::new(5, 0, 0)], "stone");
+    }
+
+    #[test]
+    fn test_merge_with_skip_air() {
+        let (mut region, other) = merge_test_regions();
+
+        region.merge_with(&other, Vector3::new(0, 0, 0), MergeStrategy::SkipAir);
+
+        assert_eq!(region.blocks().len(), 4);
+        assert_eq!(region.blocks()[&Vector3::new(0, 0, 0)], "dirt");
+        assert_eq!(region.blocks()[&Vector3::new(1, 0, 0)], "stone");
+        assert_eq!(region.blocks()[&Vector3::new(2, 0, 0)], "dirt");
+        assert_eq!(region.blocks()[&Vector3::new(5, 0, 0)], "stone");
+    }
+
+    #[test]
+    fn test_blocks_by_distance() {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(3, 0, 0), "stone");
+        region.set_block(Vector3::new(0, 0, 0), "dirt");
+        region.set_block(Vector3::new(1, 1, 1), "basalt");
+        region.set_block(Vector3::new(-1, 0, 0), "granite");
+        region.set_block(Vector3::new(0, 0, -2), "andesite");
+
+        let sorted: Vec<_> = region
+            .blocks_by_distance(Vector3::new(0, 0, 0))
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect();
+
+        assert_eq!(
+            sorted,
+            vec![
+                Vector3::new(0, 0, 0),
+                Vector3::new(-1, 0, 0),
+                Vector3::new(1, 1, 1),
+                Vector3::new(0, 0, -2),
+                Vector3::new(3, 0, 0),
+            ]
+        );
+
+        assert_eq!(
+            region.blocks_by_distance(Vector3::new(3, 0, 0))[0],
+            (Vector3::new(3, 0, 0), &BlockState::new("stone", None))
+        );
+    }
+
+    #[test]
+    fn test_mark_air() {
+        let mut region = Region::new();
+
+        region.volume = Volume::new(Vector3::new(10, 20, 30), Vector3::new(1, 1, 1));
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(2, 1, 0), "stone");
+        region.set_block(Vector3::new(1, 2, 3), "dirt");
+
+        let volume = region.volume().volume() as usize;
+
+        let marked = region.mark_air(BlockState::new("glass", None));
+
+        assert_eq!(marked, volume - 3);
+        assert_eq!(region.blocks().len(), volume);
+        assert_eq!(region.count_of_block("glass"), volume - 3);
+        assert_eq!(region.volume().volume() as usize, volume);
+        assert_eq!(
+            region.blocks().get(&Vector3::new(1, 2, 3)),
+            Some(&BlockState::new("dirt", None))
+        );
+
+        assert_eq!(region.mark_air(BlockState::new("glass", None)), 0);
+        assert_eq!(region.mark_air(BlockState::new("air", None)), 0);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let mut region = Region::new();
+
+        region.volume = Volume::new(Vector3::new(5, 5, 5), Vector3::new(1, 1, 1));
+
+        for x in 0..4 {
+            region.set_block(Vector3::new(x, 0, 0), "stone");
+        }
+
+        region.set_block(Vector3::new(0, 1, 0), "stone");
+        region.set_block(Vector3::new(3, 0, 1), "dirt");
+        region.set_block(Vector3::new(3, 0, 2), "stone");
+        region.set_block(Vector3::new(2, 1, 1), "stone");
+
+        assert_eq!(
+            region.flood_fill(Vector3::new(0, 0, 0), BlockState::new("granite", None)),
+            5
+        );
+        assert_eq!(region.count_of_block("granite"), 5);
+        assert_eq!(region.count_of_block("stone"), 2);
+
+        assert_eq!(
+            region.flood_fill(Vector3::new(0, 0, 0), BlockState::new("granite", None)),
+            0
+        );
+        assert_eq!(
+            region.flood_fill(Vector3::new(10, 0, 0), BlockState::new("granite", None)),
+            0
+        );
+
+        let volume = region.volume();
+        let blocks = region.blocks().len();
+
+        let filled = region.flood_fill(Vector3::new(0, 0, 2), BlockState::new("glass", None));
+
+        assert_eq!(filled, volume.volume() as usize - blocks);
+        assert_eq!(region.volume(), volume);
+
+        assert_eq!(
+            region.flood_fill(Vector3::new(3, 0, 1), BlockState::new("air", None)),
+            1
+        );
+        assert_eq!(region.blocks().get(&Vector3::new(3, 0, 1)), None);
+    }
+
+    #[test]
+    fn test_palette() {
+        let mut region = Region::new();
+
+        assert!(region.palette().is_empty());
+
+        let observer = "observer[facing=up]".parse::<BlockState>().unwrap();
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 0, 0), observer.clone());
+        region.set_block(Vector3::new(2, 0, 0), "dirt");
+        region.set_block(Vector3::new(3, 0, 0), "stone");
+        region.set_block(Vector3::new(4, 0, 0), "glass");
+        region.set_block(Vector3::new(4, 0, 0), "air");
+
+        assert_eq!(
+            region.palette(),
+            vec![
+                BlockState::new("dirt", None),
+                observer,
+                BlockState::new("stone", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distinct_blocks_in() {
+        let mut region = Region::new();
+
+        let mut properties = HashMap::new();
+        properties.insert("axis".to_string(), "x".to_string());
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 0, 0), "stone");
+        region.set_block(Vector3::new(0, 1, 0), "basalt");
+        region.set_block(
+            Vector3::new(1, 1, 0),
+            BlockState::new("basalt", Some(properties)),
+        );
+        region.set_block(Vector3::new(5, 5, 5), "dirt");
+
+        let volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(2, 2, 2));
+
+        assert_eq!(region.distinct_blocks_in(volume), 3);
+        assert_eq!(
+            region.distinct_blocks_in(Volume::new(Vector3::new(0, 0, 0), Vector3::new(2, 1, 1))),
+            1
+        );
+        assert_eq!(
+            region.distinct_blocks_in(Volume::new(Vector3::new(0, 0, 0), Vector3::new(6, 6, 6))),
+            4
+        );
+        assert_eq!(
+            region.distinct_blocks_in(Volume::new(Vector3::new(2, 2, 2), Vector3::new(2, 2, 2))),
+            0
+        );
+    }
+
+    #[test]
+    fn test_map_blocks() {
+        let mut region = Region::new();
+
+        let waterlogged_slab = BlockState::new("oak_slab", None)
+            .with_property("type", "bottom")
+            .with_property("waterlogged", "true");
+
+        region.set_block(Vector3::new(0, 0, 0), waterlogged_slab.clone());
+        region.set_block(Vector3::new(1, 0, 0), waterlogged_slab);
+        region.set_block(
+            Vector3::new(2, 0, 0),
+            BlockState::new("oak_stairs", None).with_property("waterlogged", "false"),
+        );
+        region.set_block(Vector3::new(3, 0, 0), "stone");
+        region.set_block(Vector3::new(4, 0, 0), "water");
+
+        region.map_blocks(|block| {
+            if *block == "water" {
+                return BlockState::new("air", None);
+            }
+
+            let mut block = block.clone();
+            block.properties.remove("waterlogged");
+            block
+        });
+
+        assert_eq!(region.blocks().len(), 4);
+        assert!(region
+            .blocks()
+            .values()
+            .all(|block| !block.properties.contains_key("waterlogged")));
+        assert_eq!(
+            region.blocks()[&Vector3::new(1, 0, 0)],
+            BlockState::new("oak_slab", None).with_property("type", "bottom")
+        );
+        assert_eq!(region.blocks()[&Vector3::new(3, 0, 0)], "stone");
+        assert_eq!(region.blocks().get(&Vector3::new(4, 0, 0)), None);
+        assert_eq!(region.volume().size(), Vector3::new(4, 1, 1));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut region = Region::new();
+
+        region.volume = Volume::new(Vector3::new(0, 60, 0), Vector3::new(1, 1, 1));
+
+        for y in 0..8 {
+            region.set_block(Vector3::new(0, y, 0), "stone");
+            region.set_block(Vector3::new(1, y, 0), "water");
+        }
+
+        region.retain(|pos, _| pos.y >= 64);
+
+        assert_eq!(region.blocks().len(), 8);
+        assert!(region.iter().all(|(pos, _)| pos.y >= 64));
+        assert_eq!(
+            region.volume(),
+            Volume::new(Vector3::new(0, 60, 0), Vector3::new(2, 8, 1))
+        );
+
+        region.retain(|_, block| *block != "water");
+
+        assert_eq!(region.count_of_block("water"), 0);
+        assert_eq!(region.count_of_block("stone"), 4);
+        assert_eq!(
+            region.bounds_of(|_| true).unwrap().origin(),
+            Vector3::new(0, 4, 0)
+        );
+    }
+
+    #[test]
+    fn test_remap_ids() {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(0, 0, 0), "grass_path");
+        region.set_block(Vector3::new(1, 0, 0), "grass_path");
+        region.set_block(
+            Vector3::new(2, 0, 0),
+            "minecraft:sign[rotation=4]".parse::<BlockState>().unwrap(),
+        );
+        region.set_block(Vector3::new(3, 0, 0), "stone");
+
+        let mut mapping = HashMap::new();
+        mapping.insert("minecraft:grass_path".to_string(), "Dirt_Path".to_string());
+        mapping.insert("sign".to_string(), "oak_sign".to_string());
+
+        assert_eq!(region.remap_ids(&mapping), 3);
+        assert_eq!(region.count_of_block("dirt_path"), 2);
+        assert_eq!(region.count_of_block("grass_path"), 0);
+        assert_eq!(
+            region.blocks()[&Vector3::new(2, 0, 0)].to_string(),
+            "minecraft:oak_sign[rotation=4]"
+        );
+        assert_eq!(region.blocks()[&Vector3::new(3, 0, 0)], "stone");
+        assert_eq!(region.remap_ids(&mapping), 0);
+    }
+
+    #[test]
+    fn test_replace_layer() {
+        let mut region = Region::new();
+
+        for x in 0..3 {
+            for y in 0..3 {
+                region.set_block(Vector3::new(x, y, 0), "oak_planks");
+            }
+        }
+
+        region.set_block(Vector3::new(1, 1, 0), "stone");
+
+        let replaced = region.replace_layer(
+            1,
+            &BlockState::new("oak_planks", None),
+            BlockState::new("spruce_planks", None),
+        );
+
+        assert_eq!(replaced, 2);
+        assert_eq!(region.count_of_block("spruce_planks"), 2);
+        assert_eq!(region.blocks()[&Vector3::new(1, 1, 0)], "stone");
+
+        for x in 0..3 {
+            assert_eq!(region.blocks()[&Vector3::new(x, 0, 0)], "oak_planks");
+            assert_eq!(region.blocks()[&Vector3::new(x, 2, 0)], "oak_planks");
+        }
+
+        let removed = region.replace_layer(
+            0,
+            &BlockState::new("oak_planks", None),
+            BlockState::new("air", None),
+        );
+
+        assert_eq!(removed, 3);
+        assert_eq!(region.blocks().len(), 6);
+    }
+
+    #[test]
+    fn test_remap_palette_indices() {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 0, 0), "dirt");
+        region.set_block(Vector3::new(2, 0, 0), "dirt");
+        region.set_block(Vector3::new(3, 0, 0), "granite");
+
+        // The palette is [air, dirt, granite, stone]
+        region.remap_palette_indices(&[0, 3, 0, 1]).unwrap();
+
+        assert_eq!(
+            region.blocks().get(&Vector3::new(0, 0, 0)),
+            Some(&BlockState::new("dirt", None))
+        );
+        assert_eq!(
+            region.blocks().get(&Vector3::new(1, 0, 0)),
+            Some(&BlockState::new("stone", None))
+        );
+        assert_eq!(
+            region.blocks().get(&Vector3::new(2, 0, 0)),
+            Some(&BlockState::new("stone", None))
+        );
+        assert_eq!(region.blocks().get(&Vector3::new(3, 0, 0)), None);
 
-        Region::new_from_nbt(root).unwrap();
+        assert!(matches!(
+            region.remap_palette_indices(&[0, 1]),
+            Err(PaletteRemapError::WrongLength {
+                expected: 3,
+                found: 2
+            })
+        ));
+        assert!(matches!(
+            region.remap_palette_indices(&[0, 1, 3]),
+            Err(PaletteRemapError::IndexOutOfRange(3))
+        ));
     }
 }
@@ -1,18 +1,66 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use quartz_nbt::{NbtCompound, NbtList, NbtTag};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
-use crate::{volume::Volume, BlockState, BlockStateParseError, Region, Vector3};
+use crate::{
+    volume::Volume, BlockState, BlockStateParseError, Region, RegionParseError, RegionWriteError,
+    Vector3,
+};
+
+/// The most bits a block can take up in the packed block states array, since litematica reads each entry into an int
+pub(super) const MAX_BITS: u64 = 32;
+
+/// Block states arrays with fewer longs than this are packed on one thread, since splitting up the work would cost more than it saves
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 4096;
+
+/// The amount of longs of the block states array each thread packs at a time when packing in parallel
+#[cfg(feature = "rayon")]
+const LONGS_PER_CHUNK: usize = 1024;
 
 impl Region {
+    /// The amount of bits each block takes up in the packed block states array, which is never less than 2
+    ///
+    /// Palettes should always contain at least air, but empty palettes get 2 bits too instead of underflowing
     pub(super) fn calculate_bits(parsed_palette_length: usize) -> u64 {
-        (usize::BITS - (parsed_palette_length - 1).leading_zeros()).max(2) as u64
+        (usize::BITS - parsed_palette_length.saturating_sub(1).leading_zeros()).max(2) as u64
+    }
+
+    /// Like [calculate_bits()](Region::calculate_bits), but errors if the palette is too large for its indices to be packed
+    pub(super) fn calculate_bits_checked(palette_length: usize) -> Result<u64, RegionWriteError> {
+        let bits = Region::calculate_bits(palette_length);
+
+        if bits > MAX_BITS {
+            return Err(RegionWriteError::PaletteTooLarge(palette_length));
+        }
+
+        Ok(bits)
     }
 
     pub(super) fn calculate_amt_of_longs(region_volume: i32, bits: u64) -> i32 {
-        let bits_required = region_volume * bits as i32;
+        // Counted in an i64 since the amount of bits overflows an i32 for large volumes, even though the amount of longs doesn't
+        let bits_required = region_volume as i64 * bits as i64;
+
+        (bits_required / 64 + if bits_required % 64 == 0 { 0 } else { 1 }) as i32
+    }
+
+    pub(super) fn validate_block_states_length(
+        block_states: &[i64],
+        region_volume: i32,
+        bits: u64,
+    ) -> Result<(), RegionWriteError> {
+        let expected = Region::calculate_amt_of_longs(region_volume, bits) as usize;
+
+        if block_states.len() != expected {
+            return Err(RegionWriteError::BlockStatesLengthMismatch {
+                expected,
+                found: block_states.len(),
+            });
+        }
 
-        bits_required / 64 + if bits_required % 64 == 0 { 0 } else { 1 }
+        Ok(())
     }
 
     pub(super) fn parse_palette(
@@ -33,12 +81,13 @@ impl Region {
         Ok(parsed_palette)
     }
 
+    /// Unpack the block states array into a map of positions to palette indices, erroring if an entry points past the end of the palette
     pub(super) fn unpack_packed_array(
         array: &[i64],
         palette: &Vec<BlockState>,
         bits_per_position: u64,
         region_size: Vector3<i32>,
-    ) -> HashMap<Vector3<i32>, BlockState> {
+    ) -> Result<HashMap<Vector3<i32>, usize>, RegionParseError> {
         let blocks = (array.len() as u64 * 64 / bits_per_position).min(region_size.volume() as u64);
 
         let mut unpacked = HashMap::new();
@@ -52,12 +101,65 @@ impl Region {
             let palette_index =
                 Region::get_index_out_of_packed_array(array, block, bits_per_position);
 
-            if palette[palette_index] != BlockState::new("air", None) {
-                unpacked.insert(coords, palette[palette_index].clone());
+            let block = palette
+                .get(palette_index)
+                .ok_or(RegionParseError::MalformedBlockData)?;
+
+            if block != BlockState::air() {
+                unpacked.insert(coords, palette_index);
             }
         }
 
-        unpacked
+        Ok(unpacked)
+    }
+
+    pub(super) fn unpack_varint_array(array: &[i8]) -> Result<Vec<usize>, RegionParseError> {
+        let mut values = Vec::new();
+
+        let mut value = 0;
+        let mut shift = 0;
+
+        for byte in array {
+            let byte = *byte as u8;
+
+            value |= ((byte & 0b0111_1111) as usize) << shift;
+
+            if byte & 0b1000_0000 == 0 {
+                values.push(value);
+
+                value = 0;
+                shift = 0;
+            } else {
+                shift += 7;
+
+                if shift >= 35 {
+                    return Err(RegionParseError::MalformedBlockData);
+                }
+            }
+        }
+
+        if shift != 0 {
+            return Err(RegionParseError::MalformedBlockData);
+        }
+
+        Ok(values)
+    }
+
+    pub(crate) fn pack_varint_array(values: &[usize]) -> Vec<i8> {
+        let mut array = Vec::with_capacity(values.len());
+
+        for value in values {
+            let mut value = *value;
+
+            while value >= 0b1000_0000 {
+                array.push(((value & 0b0111_1111) | 0b1000_0000) as u8 as i8);
+                value >>= 7;
+            }
+
+            array.push(value as i8);
+        }
+
+        array
     }
 
     pub(super) fn get_index_out_of_packed_array(
@@ -65,6 +167,8 @@ impl Region {
         position_in_array: u64,
         bits_per_position: u64,
     ) -> usize {
+        debug_assert!(bits_per_position <= MAX_BITS);
+
         let pos = position_in_array * bits_per_position;
 
         let pos_in_long = pos % 64;
@@ -72,7 +176,8 @@ impl Region {
 
         let bitmap = ((1_i64 << bits_per_position) - 1_i64).rotate_left(pos_in_long as u32);
 
-        let mut value = (array[index] & bitmap) >> pos_in_long;
+        // Shifted as unsigned so a value in the highest bits of the long doesn't get sign extended
+        let mut value = ((array[index] & bitmap) as u64 >> pos_in_long) as i64;
 
         if index < array.len() - 1 {
             let amt_to_shift = 64 - pos_in_long as u32;
@@ -90,6 +195,8 @@ impl Region {
         position_in_array: u64,
         bits_per_position: u64,
     ) {
+        debug_assert!(bits_per_position <= MAX_BITS);
+
         let pos = position_in_array * bits_per_position;
 
         let pos_in_long = pos % 64;
@@ -152,25 +259,58 @@ impl Region {
         }
     }
 
-    pub(super) fn generate_palette_nbt(
-        blocks: &HashMap<Vector3<i32>, BlockState>,
-    ) -> Vec<BlockState> {
-        let palette: HashSet<_> = blocks.values().collect();
+    /// Generate the palette used when saving the region, along with the index in it of each entry of the region's own palette
+    ///
+    /// Only goes through the blocks once, and only clones the block states that are used
+    pub(super) fn generate_palette(&self) -> (Vec<BlockState>, Vec<usize>) {
+        let mut used = vec![false; self.palette.len()];
+
+        for index in self.blocks.values() {
+            used[*index] = true;
+        }
+
+        let mut entries: Vec<_> = (0..self.palette.len()).filter(|v| used[*v]).collect();
+
+        entries.sort_by(|a, b| self.palette[*a].palette_cmp(&self.palette[*b]));
 
-        let mut palette_list: Vec<_> = palette.iter().map(|v| (**v).clone()).collect();
+        let mut palette = vec![BlockState::air().clone()];
+        let mut mapping = vec![0; self.palette.len()];
+
+        for entry in entries {
+            // Palettes read from files can contain the same block state more than once
+            if palette[palette.len() - 1] != self.palette[entry] {
+                palette.push(self.palette[entry].clone());
+            }
 
-        palette_list.insert(0, BlockState::new("air", None));
+            mapping[entry] = palette.len() - 1;
+        }
 
-        palette_list
+        (palette, mapping)
     }
 
+    /// Pack the region's blocks into a block states array for the volume given, in parallel if the `rayon` feature is enabled and the array is large enough
     pub(super) fn generate_block_states_nbt(
         &self,
         region_volume: Volume,
-        palette: &Vec<BlockState>,
-    ) -> Vec<i64> {
-        let bits = Region::calculate_bits(palette.len());
+        bits: u64,
+        mapping: &[usize],
+    ) -> Result<Vec<i64>, RegionWriteError> {
+        #[cfg(feature = "rayon")]
+        if Region::calculate_amt_of_longs(region_volume.volume(), bits) as usize
+            >= PARALLEL_THRESHOLD
+        {
+            return self.generate_block_states_nbt_parallel(region_volume, bits, mapping);
+        }
+
+        self.generate_block_states_nbt_serial(region_volume, bits, mapping)
+    }
 
+    pub(super) fn generate_block_states_nbt_serial(
+        &self,
+        region_volume: Volume,
+        bits: u64,
+        mapping: &[usize],
+    ) -> Result<Vec<i64>, RegionWriteError> {
         let longs = Region::calculate_amt_of_longs(region_volume.volume(), bits);
 
         let mut block_states: Vec<i64> = Vec::with_capacity(longs as usize);
@@ -181,22 +321,108 @@ impl Region {
 
         let size = region_volume.size();
 
-        for (block_pos, value) in self.blocks.iter() {
+        for (block_pos, index) in self.blocks.iter() {
+            let index_in_array = Region::coords_to_index(
+                size,
+                *block_pos - region_volume.origin() + self.volume.origin(),
+            )
+            .ok_or(RegionWriteError::BlockOutsideVolume(*block_pos))?;
+
             Region::set_index_in_packed_array(
                 &mut block_states,
-                palette.iter().position(|v| v == value).unwrap() as i64,
-                match Region::coords_to_index(
-                    size,
-                    *block_pos - region_volume.origin() + self.volume.origin(),
-                ) {
-                    Some(v) => v,
-                    None => unreachable!(),
-                },
+                mapping[*index] as i64,
+                index_in_array,
                 bits,
             )
         }
 
+        Ok(block_states)
+    }
+
+    /// Like [generate_block_states_nbt_serial()](Region::generate_block_states_nbt_serial), but packs the longs in parallel
+    ///
+    /// The array is split up into chunks of longs, and each chunk is packed on its own from the entries that overlap it. Entries can be split across two longs, so an entry on the edge of two chunks gets written to both, with each chunk only keeping its own bits. That way no two threads ever write to the same long
+    #[cfg(feature = "rayon")]
+    pub(super) fn generate_block_states_nbt_parallel(
+        &self,
+        region_volume: Volume,
+        bits: u64,
+        mapping: &[usize],
+    ) -> Result<Vec<i64>, RegionWriteError> {
+        let longs = Region::calculate_amt_of_longs(region_volume.volume(), bits);
+
+        let size = region_volume.size();
+        let origin = region_volume.origin();
+        let own_origin = self.volume.origin();
+
+        // Volumes are at most i32::MAX blocks, and palettes can't have more than 2^32 entries, so both fit in a u32
+        let mut entries = self
+            .blocks
+            .par_iter()
+            .map(|(block_pos, index)| {
+                let index_in_array =
+                    Region::coords_to_index(size, *block_pos - origin + own_origin)
+                        .ok_or(RegionWriteError::BlockOutsideVolume(*block_pos))?;
+
+                Ok((index_in_array as u32, mapping[*index] as u32))
+            })
+            .collect::<Result<Vec<_>, RegionWriteError>>()?;
+
+        // Sorted so each chunk can find the entries that overlap it
+        entries.par_sort_unstable_by_key(|(index_in_array, _)| *index_in_array);
+
+        let mut block_states = vec![0_i64; longs as usize];
+
         block_states
+            .par_chunks_mut(LONGS_PER_CHUNK)
+            .enumerate()
+            .for_each(|(chunk, longs)| {
+                let first_bit = (chunk * LONGS_PER_CHUNK * 64) as u64;
+                let end_bit = first_bit + longs.len() as u64 * 64;
+
+                // The first entry that ends inside of the chunk, which may have started in the previous one
+                let first = entries.partition_point(|(index_in_array, _)| {
+                    (*index_in_array as u64 + 1) * bits <= first_bit
+                });
+
+                for (index_in_array, value) in entries[first..]
+                    .iter()
+                    .take_while(|(index_in_array, _)| (*index_in_array as u64) * bits < end_bit)
+                {
+                    Region::pack_entry(
+                        longs,
+                        first_bit,
+                        *index_in_array as u64 * bits,
+                        *value as u64,
+                        bits,
+                    );
+                }
+            });
+
+        Ok(block_states)
+    }
+
+    /// Write an entry starting at bit `bit` of the block states array into a chunk of it starting at bit `first_bit`, leaving out the bits of the entry outside of the chunk
+    #[cfg(feature = "rayon")]
+    fn pack_entry(longs: &mut [i64], first_bit: u64, bit: u64, value: u64, bits: u64) {
+        for long_index in bit / 64..=(bit + bits - 1) / 64 {
+            let long = match (long_index * 64)
+                .checked_sub(first_bit)
+                .and_then(|start| longs.get_mut((start / 64) as usize))
+            {
+                Some(v) => v,
+                None => continue,
+            };
+
+            // Negative when the entry started in the previous long, so only its highest bits are in this one
+            let offset = bit as i64 - (long_index * 64) as i64;
+
+            *long |= if offset >= 0 {
+                value << offset
+            } else {
+                value >> -offset
+            } as i64;
+        }
     }
 }
 
@@ -206,6 +432,7 @@ mod tests {
 
     #[test]
     fn test_bits_required() {
+        assert_eq!(Region::calculate_bits(0), 2);
         assert_eq!(Region::calculate_bits(0b1), 2);
         assert_eq!(Region::calculate_bits(0b10), 2);
         assert_eq!(Region::calculate_bits(0b11), 2);
@@ -215,6 +442,53 @@ mod tests {
         assert_eq!(Region::calculate_bits(0b10101010000), 11);
     }
 
+    #[test]
+    fn test_calculate_bits_checked() {
+        assert_eq!(Region::calculate_bits_checked(3).unwrap(), 2);
+        assert_eq!(Region::calculate_bits_checked(70_000).unwrap(), 17);
+        assert_eq!(Region::calculate_bits_checked(1 << 32).unwrap(), 32);
+
+        assert!(matches!(
+            Region::calculate_bits_checked((1 << 32) + 1),
+            Err(RegionWriteError::PaletteTooLarge(len)) if len == (1 << 32) + 1
+        ));
+        assert!(matches!(
+            Region::calculate_bits_checked(usize::MAX),
+            Err(RegionWriteError::PaletteTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_large_palette() {
+        let mut region = Region::new();
+
+        for i in 0..70_000 {
+            region.set_block(
+                Vector3::new(i % 256, 0, i / 256),
+                BlockState::new("note_block", None).with_property("id", &i.to_string()),
+            );
+        }
+
+        let (palette, mapping) = region.generate_palette();
+        let bits = Region::calculate_bits_checked(palette.len()).unwrap();
+
+        assert_eq!(bits, 17);
+
+        let volume = region.volume();
+        let block_states = region
+            .generate_block_states_nbt(volume, bits, &mapping)
+            .unwrap();
+
+        let unpacked =
+            Region::unpack_packed_array(&block_states, &palette, bits, volume.size()).unwrap();
+
+        assert_eq!(unpacked.len(), 70_000);
+
+        for (pos, index) in unpacked {
+            assert_eq!(&palette[index], &region[pos]);
+        }
+    }
+
     #[test]
     fn test_parse_palette() {
         let mut list = NbtList::new();
@@ -301,20 +575,64 @@ mod tests {
 
         let palette = vec![BlockState::new("air", None), BlockState::new("stone", None)];
 
-        let unpacked = Region::unpack_packed_array(array, &palette, 2, Vector3::new(2, 4, 4));
+        let unpacked =
+            Region::unpack_packed_array(array, &palette, 2, Vector3::new(2, 4, 4)).unwrap();
 
         println!("{:?}", unpacked);
 
         assert_eq!(
-            unpacked.get(&Vector3::new(0, 0, 0)),
+            unpacked.get(&Vector3::new(0, 0, 0)).map(|v| &palette[*v]),
             Some(&BlockState::new("stone", None))
         );
         assert_eq!(unpacked.get(&Vector3::new(1, 0, 0)), None);
         assert_eq!(
-            unpacked.get(&Vector3::new(0, 1, 2)),
+            unpacked.get(&Vector3::new(0, 1, 2)).map(|v| &palette[*v]),
             Some(&BlockState::new("stone", None))
         );
         assert_eq!(unpacked.get(&Vector3::new(1, 1, 1)), None);
+
+        // The fourth entry is 3, but the palette only has 3 block states
+        let palette = vec![
+            BlockState::new("air", None),
+            BlockState::new("stone", None),
+            BlockState::new("dirt", None),
+        ];
+
+        assert!(matches!(
+            Region::unpack_packed_array(&[0b11_00_10_01], &palette, 2, Vector3::new(4, 1, 1)),
+            Err(RegionParseError::MalformedBlockData)
+        ));
+        assert_eq!(
+            Region::unpack_packed_array(&[0b10_00_10_01], &palette, 2, Vector3::new(4, 1, 1))
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_unpack_varint_array() {
+        assert_eq!(
+            Region::unpack_varint_array(&[0x01, 0x00, 0x7f_u8 as i8]).unwrap(),
+            vec![1, 0, 127]
+        );
+        assert_eq!(
+            Region::unpack_varint_array(&[0x80_u8 as i8, 0x01, 0xff_u8 as i8, 0x7f, 0x05]).unwrap(),
+            vec![128, 16383, 5]
+        );
+
+        assert!(Region::unpack_varint_array(&[0x80_u8 as i8]).is_err());
+        assert!(Region::unpack_varint_array(&[0x80_u8 as i8; 6]).is_err());
+    }
+
+    #[test]
+    fn test_pack_varint_array() {
+        let values = vec![0, 1, 127, 128, 300, 16383, 16384, 2_000_000];
+
+        let packed = Region::pack_varint_array(&values);
+
+        assert_eq!(&packed[..4], &[0, 1, 127, 0x80_u8 as i8]);
+        assert_eq!(Region::unpack_varint_array(&packed).unwrap(), values);
     }
 
     #[test]
@@ -325,6 +643,41 @@ mod tests {
         assert_eq!(Region::calculate_amt_of_longs(683, 5), 54);
     }
 
+    #[test]
+    fn test_calculate_amt_of_longs_rounding() {
+        // Exact multiples of 64 bits don't need an extra long
+        assert_eq!(Region::calculate_amt_of_longs(64, 1), 1);
+        assert_eq!(Region::calculate_amt_of_longs(32, 2), 1);
+        assert_eq!(Region::calculate_amt_of_longs(1024, 5), 80);
+
+        // Anything just over needs one more
+        assert_eq!(Region::calculate_amt_of_longs(65, 1), 2);
+        assert_eq!(Region::calculate_amt_of_longs(33, 2), 2);
+        assert_eq!(Region::calculate_amt_of_longs(1025, 5), 81);
+        assert_eq!(Region::calculate_amt_of_longs(i32::MAX, 32), 1 << 30);
+    }
+
+    #[test]
+    fn test_validate_block_states_length() {
+        assert!(Region::validate_block_states_length(&[0, 0], 64, 2).is_ok());
+        assert!(Region::validate_block_states_length(&[0; 54], 683, 5).is_ok());
+
+        assert!(matches!(
+            Region::validate_block_states_length(&[0], 64, 2),
+            Err(RegionWriteError::BlockStatesLengthMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            Region::validate_block_states_length(&[0; 3], 64, 2),
+            Err(RegionWriteError::BlockStatesLengthMismatch {
+                expected: 2,
+                found: 3
+            })
+        ));
+    }
+
     #[test]
     fn test_set_index_in_packed_array() {
         let array: &mut [i64] = &mut [0, 0];
@@ -341,6 +694,34 @@ mod tests {
         assert_eq!(Region::get_index_out_of_packed_array(array, 9, bits), 124);
     }
 
+    #[test]
+    fn test_set_index_in_packed_array_across_longs() {
+        let array: &mut [i64] = &mut [0, 0];
+        let bits = 5;
+
+        // Position 12 takes up bits 60 to 64, so its highest bit lands in the second long
+        Region::set_index_in_packed_array(array, 0b10111, 12, bits);
+
+        assert_eq!(array[0], 0b0111 << 60);
+        assert_eq!(array[1], 0b1);
+
+        Region::set_index_in_packed_array(array, 0b11111, 11, bits);
+        Region::set_index_in_packed_array(array, 0b11111, 13, bits);
+
+        assert_eq!(
+            Region::get_index_out_of_packed_array(array, 11, bits),
+            0b11111
+        );
+        assert_eq!(
+            Region::get_index_out_of_packed_array(array, 12, bits),
+            0b10111
+        );
+        assert_eq!(
+            Region::get_index_out_of_packed_array(array, 13, bits),
+            0b11111
+        );
+    }
+
     #[test]
     fn test_coords_to_index() {
         assert_eq!(
@@ -377,6 +758,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_coords_to_index_non_cubic() {
+        let size = Vector3::new(2, 3, 4);
+
+        assert_eq!(
+            Region::coords_to_index(size, Vector3::new(1, 2, 3)),
+            Some(23)
+        );
+        assert_eq!(
+            Region::coords_to_index(size, Vector3::new(0, 1, 0)),
+            Some(8)
+        );
+
+        for index in 0..size.volume() as u64 {
+            assert_eq!(
+                Region::coords_to_index(size, Region::index_to_coords(size, index).unwrap()),
+                Some(index)
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_palette() {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 0, 0), "granite");
+        region.set_block(Vector3::new(2, 0, 0), "dirt");
+        region.set_block(Vector3::new(2, 0, 0), "basalt");
+
+        let (palette, mapping) = region.generate_palette();
+
+        assert_eq!(
+            palette,
+            vec![
+                BlockState::new("air", None),
+                BlockState::new("basalt", None),
+                BlockState::new("granite", None),
+                BlockState::new("stone", None),
+            ]
+        );
+
+        for (pos, index) in region.blocks.iter() {
+            assert_eq!(palette[mapping[*index]], region.blocks()[pos]);
+        }
+    }
+
     #[test]
     fn test_generate_block_data() {
         let mut region = Region::new();
@@ -386,7 +814,7 @@ mod tests {
         region.set_block(Vector3::new(2, 2, 0), BlockState::new("stone_bricks", None));
         region.set_block(Vector3::new(5, 2, 1), BlockState::new("basalt", None));
 
-        let palette = Region::generate_palette_nbt(region.blocks());
+        let (palette, mapping) = region.generate_palette();
 
         assert!(palette.contains(&BlockState::new("stone", None)));
         assert!(palette.contains(&BlockState::new("basalt", None)));
@@ -396,7 +824,9 @@ mod tests {
         let volume = region.volume();
         let size = volume.size();
 
-        let block_states = region.generate_block_states_nbt(volume, &palette);
+        let block_states = region
+            .generate_block_states_nbt(volume, 2, &mapping)
+            .unwrap();
 
         assert_eq!(block_states.len(), 2);
         assert_eq!(
@@ -440,4 +870,71 @@ mod tests {
             BlockState::new("air", None)
         );
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_generate_block_data_parallel() {
+        let mut region = Region::new();
+
+        for i in 0..5000 {
+            let block = match i % 7 {
+                0 => "stone",
+                1 | 4 => "granite",
+                2 => "air",
+                3 => "dirt",
+                _ => "basalt",
+            };
+
+            region.set_block(Vector3::new(i % 23, i / 300, (i / 23) % 13), block);
+        }
+
+        for i in 0..40 {
+            region.set_block(
+                Vector3::new(i, 20, 0),
+                BlockState::new("note_block", None).with_property("note", &i.to_string()),
+            );
+        }
+
+        let (palette, mapping) = region.generate_palette();
+        let volume = region.volume();
+
+        // Checks bit counts that do and don't divide 64, so some entries get split across longs
+        for bits in [Region::calculate_bits(palette.len()), 7, 13, 32] {
+            assert_eq!(
+                region
+                    .generate_block_states_nbt_parallel(volume, bits, &mapping)
+                    .unwrap(),
+                region
+                    .generate_block_states_nbt_serial(volume, bits, &mapping)
+                    .unwrap()
+            );
+        }
+
+        let too_small = Volume::new(Vector3::new(0, 0, 0), Vector3::new(2, 1, 1));
+
+        assert!(matches!(
+            region.generate_block_states_nbt_parallel(too_small, 2, &mapping),
+            Err(RegionWriteError::BlockOutsideVolume(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_block_data_outside_volume() {
+        let mut region = Region::new();
+
+        region.set_block(Vector3::new(0, 0, 0), BlockState::new("stone", None));
+        region.set_block(Vector3::new(3, 0, 0), BlockState::new("stone", None));
+
+        let (_, mapping) = region.generate_palette();
+
+        let too_small = Volume::new(Vector3::new(0, 0, 0), Vector3::new(2, 1, 1));
+
+        assert!(matches!(
+            region.generate_block_states_nbt(too_small, 2, &mapping),
+            Err(RegionWriteError::BlockOutsideVolume(pos)) if pos == Vector3::new(3, 0, 0)
+        ));
+        assert!(region
+            .generate_block_states_nbt(region.volume(), 2, &mapping)
+            .is_ok());
+    }
 }
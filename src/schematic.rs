@@ -1,12 +1,94 @@
 use quartz_nbt::{
     io::{self, Flavor},
-    NbtCompound, NbtTag,
+    NbtCompound,
 };
-use std::{collections::HashMap, io::Read};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    io::{Read, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    volume::Volume, BlockState, LazySchematic, LitematicParseError, LitematicWriteError, Region,
+    RegionNameError, ValidationWarning, Vector3,
+};
+
+/// Data versions of minecraft releases, and the names of those releases
+const KNOWN_DATA_VERSIONS: &[(i32, &str)] = &[
+    (1343, "1.12.2"),
+    (1519, "1.13"),
+    (1628, "1.13.1"),
+    (1631, "1.13.2"),
+    (1952, "1.14"),
+    (1957, "1.14.1"),
+    (1963, "1.14.2"),
+    (1968, "1.14.3"),
+    (1976, "1.14.4"),
+    (2225, "1.15"),
+    (2227, "1.15.1"),
+    (2230, "1.15.2"),
+    (2566, "1.16"),
+    (2567, "1.16.1"),
+    (2578, "1.16.2"),
+    (2580, "1.16.3"),
+    (2584, "1.16.4"),
+    (2586, "1.16.5"),
+    (2724, "1.17"),
+    (2730, "1.17.1"),
+    (2860, "1.18"),
+    (2865, "1.18.1"),
+    (2975, "1.18.2"),
+    (3105, "1.19"),
+    (3117, "1.19.1"),
+    (3120, "1.19.2"),
+    (3218, "1.19.3"),
+    (3337, "1.19.4"),
+    (3463, "1.20"),
+    (3465, "1.20.1"),
+    (3578, "1.20.2"),
+    (3698, "1.20.3"),
+    (3700, "1.20.4"),
+    (3837, "1.20.5"),
+    (3839, "1.20.6"),
+    (3953, "1.21"),
+    (3955, "1.21.1"),
+    (4080, "1.21.2"),
+    (4082, "1.21.3"),
+    (4189, "1.21.4"),
+];
+
+/// The most distinct block states a region can have before [Schematic::validate()] warns about it
+const MAX_PALETTE_SIZE: usize = 1 << 16;
+
+/// Options for how a schematic gets written by [to_buffer_with_options()](Schematic::to_buffer_with_options)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveOptions {
+    /// Write the current time as the time the schematic was last modified, instead of its `time_modified` field
+    pub refresh_modified: bool,
+    /// Write the amount of regions the schematic has now as its region count, instead of the count it was read with
+    ///
+    /// Schematics that weren't read from a file always write the amount of regions they have
+    pub recompute_region_count: bool,
+    /// Write the regions in alphabetical order of their names, which is useful for getting the same bytes out each time
+    ///
+    /// Otherwise they're written in whatever order [regions](Schematic::regions) gives them in
+    pub sort_regions: bool,
+}
 
-use crate::{volume::Volume, LitematicParseError, Region};
+impl Default for SaveOptions {
+    /// The options used by [to_buffer()](Schematic::to_buffer), which keep `time_modified` and recompute the region count
+    fn default() -> SaveOptions {
+        SaveOptions {
+            refresh_modified: false,
+            recompute_region_count: true,
+            sort_regions: false,
+        }
+    }
+}
 
 /// A struct that stores the data in a schematic
+#[derive(Clone)]
 pub struct Schematic {
     /// A schematic's author
     pub author: String,
@@ -14,15 +96,51 @@ pub struct Schematic {
     pub description: String,
     /// A schematic's name
     pub name: String,
-    time_created: i64,
+    pub(crate) time_created: i64,
     /// Last time a schematic was modified, in milliseconds since 1970
     pub time_modified: i64,
     /// A hashmap of the schematic's regions
     pub regions: HashMap<String, Region>,
-    data_version: i32,
+    pub(crate) data_version: i32,
+    pub(crate) region_count: Option<i32>,
+    /// The thumbnail shown for a schematic in litematica's menus, stored as one ARGB color per pixel
+    ///
+    /// Use [preview_image_pixels()](Schematic::preview_image_pixels) to split the colors into their channels
+    pub preview_image_data: Option<Vec<i32>>,
+    /// Top level tags in the schematic's NBT data that this crate doesn't know about, which are written back out unchanged when the schematic is saved
+    pub extra: NbtCompound,
 }
 
 impl Schematic {
+    /// The data version of minecraft 1.12.2, the oldest release this crate knows the data version of
+    pub const MINIMUM_DATA_VERSION: i32 = Schematic::DATA_VERSION_1_12_2;
+    /// The data version of minecraft 1.12.2
+    pub const DATA_VERSION_1_12_2: i32 = 1343;
+    /// The data version of minecraft 1.13.2
+    pub const DATA_VERSION_1_13_2: i32 = 1631;
+    /// The data version of minecraft 1.14.4
+    pub const DATA_VERSION_1_14_4: i32 = 1976;
+    /// The data version of minecraft 1.15.2
+    pub const DATA_VERSION_1_15_2: i32 = 2230;
+    /// The data version of minecraft 1.16.5
+    pub const DATA_VERSION_1_16_5: i32 = 2586;
+    /// The data version of minecraft 1.17.1, used by schematics created with [new()](Schematic::new)
+    pub const DATA_VERSION_1_17_1: i32 = 2730;
+    /// The data version of minecraft 1.18.2
+    pub const DATA_VERSION_1_18_2: i32 = 2975;
+    /// The data version of minecraft 1.19.4
+    pub const DATA_VERSION_1_19_4: i32 = 3337;
+    /// The data version of minecraft 1.20.1
+    pub const DATA_VERSION_1_20_1: i32 = 3465;
+    /// The data version of minecraft 1.20.4
+    pub const DATA_VERSION_1_20_4: i32 = 3700;
+    /// The data version of minecraft 1.20.6
+    pub const DATA_VERSION_1_20_6: i32 = 3839;
+    /// The data version of minecraft 1.21.1
+    pub const DATA_VERSION_1_21_1: i32 = 3955;
+    /// The data version of minecraft 1.21.4
+    pub const DATA_VERSION_1_21_4: i32 = 4189;
+
     /// Create a new schematic
     pub fn new(
         name: Option<String>,
@@ -37,7 +155,10 @@ impl Schematic {
             time_created: time_created.unwrap_or(0),
             time_modified: time_created.unwrap_or(0),
             regions: HashMap::new(),
-            data_version: 2730,
+            data_version: Schematic::DATA_VERSION_1_17_1,
+            region_count: None,
+            preview_image_data: None,
+            extra: NbtCompound::new(),
         }
     }
 
@@ -53,25 +174,74 @@ impl Schematic {
     /// # }
     /// ```
     pub fn from_buffer(data: &mut impl Read) -> Result<Schematic, LitematicParseError> {
+        LazySchematic::from_buffer(data)?.into_schematic()
+    }
+
+    /// Read a schematic from a buffer, decompressing it the way the flavor given says to
+    ///
+    /// [from_buffer()](Schematic::from_buffer) works out how the data is compressed on its own, so this is only needed when that's already known
+    pub fn from_buffer_with_flavor(
+        data: &mut impl Read,
+        flavor: Flavor,
+    ) -> Result<Schematic, LitematicParseError> {
+        LazySchematic::from_buffer_with_flavor(data, flavor)?.into_schematic()
+    }
+
+    /// Read a WorldEdit `.schem` file (the Sponge schematic format) from a buffer, putting all of its blocks into a single region
+    ///
+    /// The region is named after the schematic, or `Main` if it has no name. Block entities and entities aren't imported
+    ///
+    /// ```no_run
+    /// use litematic_editor::Schematic;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), std::io::Error> {
+    /// let schematic = Schematic::from_schem_buffer(&mut File::open("path/to/build.schem")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_schem_buffer(data: &mut impl Read) -> Result<Schematic, LitematicParseError> {
         let parsed_data = io::read_nbt(data, Flavor::GzCompressed)?.0;
 
         let version = parsed_data.get::<_, i32>("Version")?;
 
-        if version != 5 {
+        if version != 1 && version != 2 {
             return Err(LitematicParseError::UnsupportedVersionNumber(version));
         }
 
-        let metadata = parsed_data.get::<_, &NbtCompound>("Metadata")?;
+        let metadata = parsed_data.get::<_, &NbtCompound>("Metadata").ok();
 
-        Ok(Schematic {
-            author: metadata.get::<_, &String>("Author")?.clone(),
-            description: metadata.get::<_, &String>("Description")?.clone(),
-            name: metadata.get::<_, &String>("Name")?.clone(),
-            time_created: metadata.get::<_, i64>("TimeCreated")?,
-            time_modified: metadata.get::<_, i64>("TimeModified")?,
-            regions: Schematic::parse_regions(&parsed_data)?,
-            data_version: parsed_data.get::<_, i32>("MinecraftDataVersion")?,
-        })
+        let get_metadata = |name: &str| {
+            metadata
+                .and_then(|v| v.get::<_, &String>(name).ok())
+                .cloned()
+        };
+
+        let time = metadata.and_then(|v| v.get::<_, i64>("Date").ok());
+
+        let mut schematic =
+            Schematic::new(get_metadata("Name"), get_metadata("Author"), None, time);
+
+        if let Ok(data_version) = parsed_data.get::<_, i32>("DataVersion") {
+            schematic.data_version = data_version;
+        }
+
+        let region_name = if schematic.name.is_empty() {
+            "Main".to_string()
+        } else {
+            schematic.name.clone()
+        };
+
+        let region = Region::new_from_schem(&parsed_data).map_err(|error| {
+            LitematicParseError::RegionParseError {
+                region: region_name.clone(),
+                error,
+            }
+        })?;
+
+        schematic.regions.insert(region_name, region);
+
+        Ok(schematic)
     }
 
     /// Write a schematic's data to a u8 vector
@@ -80,39 +250,148 @@ impl Schematic {
     /// use litematic_editor::Schematic;
     /// use std::fs;
     ///
-    /// # fn main() -> Result<(), std::io::Error> {
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let schematic = Schematic::new(Some("example schematic".to_string()), Some("a cool person".to_string()), None, None);
     ///
-    /// let buffer = schematic.to_buffer();
+    /// let buffer = schematic.to_buffer()?;
     ///
     /// fs::write("test/path/to/new_schematic.litematic", buffer)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn to_buffer(&self) -> Vec<u8> {
-        let mut out = NbtCompound::new();
+    pub fn to_buffer(&self) -> Result<Vec<u8>, LitematicWriteError> {
+        self.to_buffer_with_options(SaveOptions::default())
+    }
+
+    /// Write a schematic's data to a u8 vector, choosing how its metadata and regions get written
+    ///
+    /// ```
+    /// use litematic_editor::{SaveOptions, Schematic};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let schematic = Schematic::new(Some("example schematic".to_string()), None, None, None);
+    ///
+    /// let buffer = schematic.to_buffer_with_options(SaveOptions {
+    ///     refresh_modified: true,
+    ///     ..SaveOptions::default()
+    /// })?;
+    ///
+    /// assert!(Schematic::from_buffer(&mut buffer.as_slice())?.time_modified > 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_buffer_with_options(
+        &self,
+        options: SaveOptions,
+    ) -> Result<Vec<u8>, LitematicWriteError> {
+        let mut out_buffer = Vec::new();
+
+        self.write_to_with_options(&mut out_buffer, options)?;
+
+        Ok(out_buffer)
+    }
+
+    /// Write a schematic's data straight into a writer, like a file, without keeping all of it in memory first
+    ///
+    /// Writes the same data as [to_buffer()](Schematic::to_buffer)
+    ///
+    /// ```
+    /// use litematic_editor::Schematic;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let schematic = Schematic::new(Some("example schematic".to_string()), None, None, None);
+    ///
+    /// schematic.write_to(&mut File::create("test/path/to/new_schematic.litematic")?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<(), LitematicWriteError> {
+        self.write_to_with_options(writer, SaveOptions::default())
+    }
+
+    /// Write a schematic's data straight into a writer, choosing how its metadata and regions get written
+    pub fn write_to_with_options(
+        &self,
+        writer: &mut impl Write,
+        options: SaveOptions,
+    ) -> Result<(), LitematicWriteError> {
+        io::write_nbt(writer, None, &self.to_nbt(options)?, Flavor::GzCompressed)?;
+
+        Ok(())
+    }
+
+    /// Write a schematic's data to a u8 vector, compressed the way the flavor given says to
+    ///
+    /// Litematica always writes gzip compressed files, which is what [to_buffer()](Schematic::to_buffer) does, but other tools may want uncompressed or zlib compressed NBT data
+    ///
+    /// ```
+    /// use litematic_editor::Schematic;
+    /// use quartz_nbt::io::Flavor;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let schematic = Schematic::new(Some("example schematic".to_string()), None, None, None);
+    ///
+    /// let buffer = schematic.to_buffer_with_flavor(Flavor::Uncompressed)?;
+    ///
+    /// assert_eq!(Schematic::from_buffer_with_flavor(&mut buffer.as_slice(), Flavor::Uncompressed)?.name, "example schematic");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_buffer_with_flavor(&self, flavor: Flavor) -> Result<Vec<u8>, LitematicWriteError> {
+        let mut out_buffer = Vec::new();
+
+        io::write_nbt(
+            &mut out_buffer,
+            None,
+            &self.to_nbt(SaveOptions::default())?,
+            flavor,
+        )?;
+
+        Ok(out_buffer)
+    }
+
+    fn to_nbt(&self, options: SaveOptions) -> Result<NbtCompound, LitematicWriteError> {
+        let mut out = self.extra.clone();
 
         let mut metadata = NbtCompound::new();
 
         metadata.insert("Name", self.name.clone());
         metadata.insert("Author", self.author.clone());
         metadata.insert("Description", self.description.clone());
-        metadata.insert("RegionCount", self.regions.len() as i32);
+        metadata.insert(
+            "RegionCount",
+            match self.region_count {
+                Some(count) if !options.recompute_region_count => count,
+                _ => self.regions.len() as i32,
+            },
+        );
         metadata.insert("TimeCreated", self.time_created);
-        metadata.insert("TimeModified", self.time_modified);
+        metadata.insert(
+            "TimeModified",
+            if options.refresh_modified {
+                Schematic::current_time().unwrap_or(self.time_modified)
+            } else {
+                self.time_modified
+            },
+        );
         metadata.insert(
             "TotalBlocks",
-            self.regions
-                .values()
-                .fold(0, |a, v| a + v.blocks().len() as i32),
+            self.total_blocks().min(i32::MAX as u64) as i32,
         );
 
         let mut regions = NbtCompound::new();
 
         let mut total_volume: Option<Volume> = None;
 
-        for (name, region) in self.regions.iter() {
-            let (encoded, volume) = region.to_nbt();
+        let mut names: Vec<_> = self.regions.keys().collect();
+
+        if options.sort_regions {
+            names.sort();
+        }
+
+        for name in names {
+            let (encoded, volume) = self.regions[name].to_nbt()?;
 
             total_volume = Some(match total_volume {
                 Some(v) => v.expand_to_fit_volume(volume),
@@ -127,38 +406,1416 @@ impl Schematic {
         metadata.insert("EnclosingSize", total_volume.size());
         metadata.insert("TotalVolume", total_volume.volume());
 
+        if let Some(preview_image_data) = &self.preview_image_data {
+            metadata.insert("PreviewImageData", preview_image_data.clone());
+        }
+
         out.insert("Metadata", metadata);
         out.insert("MinecraftDataVersion", self.data_version);
         out.insert("Version", 5);
         out.insert("Regions", regions);
 
+        Ok(out)
+    }
+
+    /// Write a schematic's data as a WorldEdit `.schem` file (the Sponge schematic format) to a u8 vector
+    ///
+    /// All the regions are flattened into a single volume enclosing all of them. Where regions overlap, the blocks of the region whose name comes last alphabetically win, though air never overwrites anything. Block entities and entities aren't exported
+    ///
+    /// Fails if the flattened volume is larger than 65535 blocks along any axis, since the format can't store that
+    pub fn to_schem_buffer(&self) -> Result<Vec<u8>, LitematicWriteError> {
+        let merged = self.merged_region();
+        let total_volume = merged.volume;
+
+        // The names are kept in the order they were given indices in, so the palette gets written the same way every time
+        let mut palette = HashMap::new();
+        let mut palette_names = vec![BlockState::air().to_string()];
+        palette.insert(BlockState::air().to_string(), 0);
+
+        let mut indices = Vec::with_capacity(total_volume.volume() as usize);
+
+        for pos in total_volume {
+            let block = match merged.blocks().get(&(pos - total_volume.origin())) {
+                Some(block) => block.to_string(),
+                None => BlockState::air().to_string(),
+            };
+
+            let index = match palette.get(&block) {
+                Some(index) => *index,
+                None => {
+                    palette.insert(block.clone(), palette_names.len());
+                    palette_names.push(block);
+
+                    palette_names.len() - 1
+                }
+            };
+
+            indices.push(index);
+        }
+
+        let size = total_volume.size();
+        let dimension = |value: i32| {
+            u16::try_from(value)
+                .map(|v| v as i16)
+                .map_err(|_| LitematicWriteError::TooLargeForSchem(size))
+        };
+
+        let mut out = NbtCompound::new();
+
+        out.insert("Version", 2);
+        out.insert("DataVersion", self.data_version);
+        out.insert("Width", dimension(size.x)?);
+        out.insert("Height", dimension(size.y)?);
+        out.insert("Length", dimension(size.z)?);
+        out.insert("Offset", total_volume.origin().into_slice().to_vec());
+        out.insert("PaletteMax", palette_names.len() as i32);
+
+        let mut palette_nbt = NbtCompound::new();
+
+        for (index, block) in palette_names.into_iter().enumerate() {
+            palette_nbt.insert(block, index as i32);
+        }
+
+        out.insert("Palette", palette_nbt);
+        out.insert("BlockData", Region::pack_varint_array(&indices));
+
+        let mut metadata = NbtCompound::new();
+
+        metadata.insert("Name", self.name.clone());
+        metadata.insert("Author", self.author.clone());
+        metadata.insert("Date", self.time_modified);
+
+        out.insert("Metadata", metadata);
+
         let mut out_buffer = Vec::new();
 
-        io::write_nbt(&mut out_buffer, None, &out, Flavor::GzCompressed).unwrap();
+        io::write_nbt(
+            &mut out_buffer,
+            Some("Schematic"),
+            &out,
+            Flavor::GzCompressed,
+        )?;
 
-        out_buffer
+        Ok(out_buffer)
     }
 
-    fn parse_regions(data: &NbtCompound) -> Result<HashMap<String, Region>, LitematicParseError> {
-        let regions_nbt =
-            if let NbtTag::Compound(regions_nbt) = data.get::<_, &NbtTag>("Regions")?.clone() {
-                regions_nbt
-            } else {
-                return Err(LitematicParseError::WrongTag("Regions".to_string()));
-            };
+    /// Convert one of the schematic's regions to a vanilla structure, the format used by structure blocks
+    ///
+    /// If no region name is given, all the regions are merged into one the same way as [to_schem_buffer()](Schematic::to_schem_buffer) does it, which drops their block entities. Returns `None` if there's no region with the name given
+    ///
+    /// ```
+    /// use litematic_editor::{Region, Schematic, Vector3};
+    ///
+    /// let mut schematic = Schematic::new(None, None, None, None);
+    ///
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "stone");
+    ///
+    /// schematic.regions.insert("main".to_string(), region);
+    ///
+    /// let structure = schematic.to_structure_nbt(Some("main")).unwrap();
+    ///
+    /// assert_eq!(structure.get::<_, i32>("DataVersion").unwrap(), 2730);
+    /// assert!(schematic.to_structure_nbt(Some("other")).is_none());
+    /// ```
+    pub fn to_structure_nbt(&self, region: Option<&str>) -> Option<NbtCompound> {
+        let mut out = match region {
+            Some(name) => self.regions.get(name)?.to_structure_nbt(),
+            None => self.merged_region().to_structure_nbt(),
+        };
+
+        out.insert("DataVersion", self.data_version);
 
-        let regions = regions_nbt.into_inner();
+        Some(out)
+    }
 
-        let mut regions_parsed = HashMap::new();
+    /// Flatten all the regions into one, where the blocks of regions whose names come later alphabetically overwrite the others
+    fn merged_region(&self) -> Region {
+        let mut names: Vec<_> = self.regions.keys().collect();
+        names.sort();
 
-        for region_unknown in regions {
-            if let NbtTag::Compound(region) = region_unknown.1 {
-                regions_parsed.insert(region_unknown.0, Region::new_from_nbt(region)?);
-            } else {
-                return Err(LitematicParseError::WrongTag(region_unknown.0));
+        let mut merged = Region::new();
+
+        merged.volume = names
+            .iter()
+            .map(|name| self.regions[*name].volume())
+            .reduce(|a, v| a.expand_to_fit_volume(v))
+            .unwrap_or_default()
+            .make_size_positive();
+
+        for name in names {
+            let region = &self.regions[name];
+            let offset = region.volume.origin() - merged.volume.origin();
+
+            for (pos, block) in region.blocks() {
+                merged.set_block(*pos + offset, block.clone());
+            }
+        }
+
+        merged
+    }
+
+    /// Move all of another schematic's regions into this one
+    ///
+    /// Regions whose names are already taken are renamed to `prefix` followed by their name. If that's taken too, ` 2`, ` 3`, and so on are added to the end until the name is free. The other schematic's metadata is dropped, this schematic's metadata is kept, and the time it was last modified is set to the current time
+    ///
+    /// ```
+    /// use litematic_editor::{Region, Schematic};
+    ///
+    /// let mut library = Schematic::new(None, None, None, None);
+    /// library.regions.insert("house".to_string(), Region::new());
+    ///
+    /// let mut other = Schematic::new(None, None, None, None);
+    /// other.regions.insert("house".to_string(), Region::new());
+    /// other.regions.insert("farm".to_string(), Region::new());
+    ///
+    /// library.merge(other, "other ");
+    ///
+    /// let mut names: Vec<_> = library.region_names().collect();
+    /// names.sort();
+    ///
+    /// assert_eq!(names, vec!["farm", "house", "other house"]);
+    /// ```
+    pub fn merge(&mut self, other: Schematic, prefix: &str) {
+        for (name, region) in other.regions {
+            let mut new_name = name.clone();
+
+            if self.regions.contains_key(&new_name) {
+                new_name = format!("{}{}", prefix, name);
+            }
+
+            let mut counter = 2;
+
+            while self.regions.contains_key(&new_name) {
+                new_name = format!("{}{} {}", prefix, name, counter);
+                counter += 1;
+            }
+
+            self.regions.insert(new_name, region);
+        }
+
+        if let Some(count) = self.region_count.as_mut() {
+            *count = self.regions.len() as i32;
+        }
+
+        if let Some(time) = Schematic::current_time() {
+            self.time_modified = time;
+        }
+    }
+
+    /// Replace every block equal to `from` with `to` in all of the schematic's regions, returning how many blocks were replaced in total
+    ///
+    /// Works the same way as [Region::replace_block()], and updates `time_modified` if any blocks were replaced
+    pub fn replace_block(&mut self, from: &BlockState, to: BlockState) -> usize {
+        let replaced = self
+            .regions
+            .values_mut()
+            .map(|region| region.replace_block(from, to.clone()))
+            .sum();
+
+        if replaced > 0 {
+            if let Some(time) = Schematic::current_time() {
+                self.time_modified = time;
+            }
+        }
+
+        replaced
+    }
+
+    /// Rename blocks in all of the schematic's regions according to the mapping given from old block ids to new ones, returning how many blocks were renamed in total
+    ///
+    /// Works the same way as [Region::remap_ids()], and updates `time_modified` if any blocks were renamed
+    pub fn remap_ids(&mut self, mapping: &HashMap<String, String>) -> usize {
+        let renamed = self
+            .regions
+            .values_mut()
+            .map(|region| region.remap_ids(mapping))
+            .sum();
+
+        if renamed > 0 {
+            if let Some(time) = Schematic::current_time() {
+                self.time_modified = time;
+            }
+        }
+
+        renamed
+    }
+
+    /// The current time in milliseconds since 1970, the way schematics store times
+    fn current_time() -> Option<i64> {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|v| v.as_millis() as i64)
+    }
+
+    /// An iterator over the names of all the schematic's regions, in an arbitrary order
+    pub fn region_names(&self) -> impl Iterator<Item = &str> {
+        self.regions.keys().map(|v| v.as_str())
+    }
+
+    /// Get the region with the given name
+    ///
+    /// ```
+    /// use litematic_editor::{Region, Schematic, Vector3};
+    ///
+    /// let mut schematic = Schematic::new(None, None, None, None);
+    /// schematic.regions.insert("main".to_string(), Region::new());
+    ///
+    /// schematic.get_region_mut("main").unwrap().set_block(Vector3::new(0, 0, 0), "stone");
+    ///
+    /// assert_eq!(schematic.get_region("main").unwrap().blocks().len(), 1);
+    /// assert!(schematic.get_region("other").is_none());
+    /// assert_eq!(schematic.region_names().collect::<Vec<_>>(), vec!["main"]);
+    /// ```
+    pub fn get_region(&self, name: &str) -> Option<&Region> {
+        self.regions.get(name)
+    }
+
+    /// Get a mutable reference to the region with the given name
+    pub fn get_region_mut(&mut self, name: &str) -> Option<&mut Region> {
+        self.regions.get_mut(name)
+    }
+
+    /// Insert a region with the given name, returning the region that had that name before if there was one
+    ///
+    /// Keeps the region count the schematic was read with in sync and updates `time_modified`. Empty names are rejected since litematica can't look up regions by them
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, RegionNameError, Schematic};
+    /// let mut schematic = Schematic::new(None, None, None, None);
+    ///
+    /// assert!(schematic.set_region("main", Region::new()).unwrap().is_none());
+    /// assert!(schematic.set_region("main", Region::new()).unwrap().is_some());
+    /// assert!(matches!(schematic.set_region("", Region::new()), Err(RegionNameError::Empty)));
+    /// assert_eq!(schematic.regions.len(), 1);
+    /// ```
+    pub fn set_region(
+        &mut self,
+        name: &str,
+        region: Region,
+    ) -> Result<Option<Region>, RegionNameError> {
+        if name.is_empty() {
+            return Err(RegionNameError::Empty);
+        }
+
+        let old = self.regions.insert(name.to_string(), region);
+
+        if let Some(count) = self.region_count.as_mut() {
+            *count = self.regions.len() as i32;
+        }
+
+        if let Some(time) = Schematic::current_time() {
+            self.time_modified = time;
+        }
+
+        Ok(old)
+    }
+
+    /// The distinct block states used in any of the schematic's regions, sorted the same way as [Region::palette()]
+    pub fn palette(&self) -> Vec<BlockState> {
+        let mut palette: Vec<_> = self
+            .regions
+            .values()
+            .flat_map(|region| region.palette())
+            .collect();
+
+        palette.sort_by(|a, b| a.palette_cmp(b));
+        palette.dedup();
+
+        palette
+    }
+
+    /// An iterator over the blocks in all of the schematic's regions excluding air, with their positions in the schematic's coordinates
+    ///
+    /// Goes through the regions one after another without collecting their blocks, so it doesn't allocate anything for large schematics. Where regions overlap, blocks at the same position are yielded once for each region
+    pub fn iter_blocks(&self) -> Box<dyn Iterator<Item = (Vector3<i32>, &BlockState)> + '_> {
+        Box::new(self.regions.values().flat_map(|region| region.iter()))
+    }
+
+    /// Count the blocks in all of the schematic's regions, excluding air, which is the count written to the `TotalBlocks` metadata tag
+    ///
+    /// Air inside the regions' volumes isn't counted, this is the sum of [Region::non_air_count()] over every region. Returned as a `u64` since huge schematics can have more blocks than fit in the `i32` the file format stores the count in. When saving, counts that don't fit are written as `i32::MAX`
+    ///
+    /// ```
+    /// use litematic_editor::{Region, Schematic, Vector3};
+    ///
+    /// let mut schematic = Schematic::new(None, None, None, None);
+    ///
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "stone");
+    /// region.set_block(Vector3::new(1, 0, 0), "air");
+    /// region.set_block(Vector3::new(2, 0, 0), "dirt");
+    ///
+    /// schematic.regions.insert("main".to_string(), region.clone());
+    /// schematic.regions.insert("other".to_string(), region);
+    ///
+    /// assert_eq!(schematic.total_blocks(), 4);
+    /// ```
+    pub fn total_blocks(&self) -> u64 {
+        self.regions
+            .values()
+            .map(|region| region.non_air_count() as u64)
+            .sum()
+    }
+
+    /// Split the colors of the schematic's preview image into their alpha, red, green, and blue channels, or `None` if it doesn't have one
+    ///
+    /// The pixels go row by row, and litematica's previews are square
+    ///
+    /// ```
+    /// # use litematic_editor::Schematic;
+    /// let mut schematic = Schematic::new(None, None, None, None);
+    /// assert_eq!(schematic.preview_image_pixels(), None);
+    ///
+    /// schematic.preview_image_data = Some(vec![0xff00ff00u32 as i32]);
+    /// assert_eq!(schematic.preview_image_pixels(), Some(vec![[0xff, 0x00, 0xff, 0x00]]));
+    /// ```
+    pub fn preview_image_pixels(&self) -> Option<Vec<[u8; 4]>> {
+        self.preview_image_data.as_ref().map(|data| {
+            data.iter()
+                .map(|pixel| (*pixel as u32).to_be_bytes())
+                .collect()
+        })
+    }
+
+    /// Check the schematic for problems that could make it get written incorrectly or fail to load in litematica, returning all the problems found
+    ///
+    /// Checks that the region count the schematic was read with matches how many regions it has, that the [volume](Region::volume) each region gets written with doesn't hold too many blocks to count, that regions don't have an absurd amount of distinct block states, and that block ids are well formed
+    ///
+    /// ```
+    /// # use litematic_editor::{Region, Schematic, ValidationWarning, Vector3};
+    /// let mut schematic = Schematic::new(None, None, None, None);
+    ///
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "stone");
+    /// schematic.regions.insert("main".to_string(), region.clone());
+    ///
+    /// assert_eq!(schematic.validate(), Ok(()));
+    ///
+    /// region.set_block(Vector3::new(1, 0, 0), "Stone Bricks");
+    /// schematic.regions.insert("main".to_string(), region);
+    ///
+    /// assert_eq!(
+    ///     schematic.validate(),
+    ///     Err(vec![ValidationWarning::MalformedBlockId {
+    ///         region: "main".to_string(),
+    ///         id: "minecraft:stone bricks".to_string(),
+    ///     }])
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ValidationWarning>> {
+        let mut warnings = Vec::new();
+
+        if let Some(stored) = self.region_count {
+            if stored < 0 || stored as usize != self.regions.len() {
+                warnings.push(ValidationWarning::RegionCountMismatch {
+                    stored,
+                    actual: self.regions.len(),
+                });
             }
         }
 
-        Ok(regions_parsed)
+        let mut names: Vec<_> = self.regions.keys().collect();
+        names.sort();
+
+        for name in names {
+            let region = &self.regions[name];
+
+            let size = region.volume().size();
+
+            if size.checked_volume().is_none() {
+                warnings.push(ValidationWarning::VolumeTooLarge {
+                    region: name.clone(),
+                    size,
+                });
+            }
+
+            let palette = region.palette();
+
+            if palette.len() > MAX_PALETTE_SIZE {
+                warnings.push(ValidationWarning::LargePalette {
+                    region: name.clone(),
+                    size: palette.len(),
+                });
+            }
+
+            let mut malformed: Vec<_> = palette
+                .iter()
+                .filter(|block| !block.has_valid_id())
+                .map(|block| block.get_block().clone())
+                .collect();
+
+            malformed.dedup();
+
+            for id in malformed {
+                warnings.push(ValidationWarning::MalformedBlockId {
+                    region: name.clone(),
+                    id,
+                });
+            }
+        }
+
+        if warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(warnings)
+        }
+    }
+
+    /// Get the name of the minecraft release this schematic was made for, based on its data version
+    ///
+    /// Returns `None` for data versions that aren't releases, or are too old or too new to be known
+    ///
+    /// ```
+    /// # use litematic_editor::Schematic;
+    /// assert_eq!(Schematic::new(None, None, None, None).minecraft_version_name(), Some("1.17.1"));
+    /// ```
+    pub fn minecraft_version_name(&self) -> Option<&'static str> {
+        KNOWN_DATA_VERSIONS
+            .iter()
+            .find(|(data_version, _)| *data_version == self.data_version)
+            .map(|(_, name)| *name)
+    }
+
+    /// The data version of the minecraft version this schematic was made for
+    ///
+    /// Minecraft uses it to upgrade the blocks in the schematic when it's loaded in a newer version of the game
+    pub fn data_version(&self) -> i32 {
+        self.data_version
+    }
+
+    /// Set the data version of the minecraft version this schematic is made for, for example to one of the `DATA_VERSION` constants
+    pub fn set_data_version(&mut self, data_version: i32) {
+        self.data_version = data_version;
+    }
+
+    /// Set the minecraft release this schematic is made for by its name, like `1.20.1`
+    ///
+    /// Returns the data version that was set, or `None` without changing anything if the release isn't known
+    ///
+    /// ```
+    /// # use litematic_editor::Schematic;
+    /// let mut schematic = Schematic::new(None, None, None, None);
+    ///
+    /// assert_eq!(schematic.set_minecraft_version("1.20.1"), Some(Schematic::DATA_VERSION_1_20_1));
+    /// assert_eq!(schematic.minecraft_version_name(), Some("1.20.1"));
+    ///
+    /// assert_eq!(schematic.set_minecraft_version("1.20.1-pre1"), None);
+    /// assert_eq!(schematic.data_version(), Schematic::DATA_VERSION_1_20_1);
+    /// ```
+    pub fn set_minecraft_version(&mut self, version: &str) -> Option<i32> {
+        let data_version = KNOWN_DATA_VERSIONS
+            .iter()
+            .find(|(_, name)| *name == version)
+            .map(|(data_version, _)| *data_version)?;
+
+        self.data_version = data_version;
+
+        Some(data_version)
+    }
+
+    /// Create a new schematic containing only the region with the given name, copying this schematic's metadata
+    ///
+    /// Returns `None` if there's no region with that name
+    ///
+    /// ```
+    /// use litematic_editor::{Region, Schematic, Vector3};
+    ///
+    /// let mut schematic = Schematic::new(Some("example schematic".to_string()), None, None, None);
+    ///
+    /// let mut region = Region::new();
+    /// region.set_block(Vector3::new(0, 0, 0), "stone");
+    ///
+    /// schematic.regions.insert("main".to_string(), region);
+    /// schematic.regions.insert("other".to_string(), Region::new());
+    ///
+    /// let isolated = schematic.isolate_region("main").unwrap();
+    ///
+    /// assert_eq!(isolated.regions.len(), 1);
+    /// assert_eq!(isolated.name, "example schematic");
+    /// assert!(schematic.isolate_region("nonexistent").is_none());
+    /// ```
+    pub fn isolate_region(&self, name: &str) -> Option<Schematic> {
+        let region = self.regions.get(name)?.clone();
+
+        let mut regions = HashMap::new();
+        regions.insert(name.to_string(), region);
+
+        Some(Schematic {
+            author: self.author.clone(),
+            description: self.description.clone(),
+            name: self.name.clone(),
+            time_created: self.time_created,
+            time_modified: self.time_modified,
+            regions,
+            data_version: self.data_version,
+            region_count: None,
+            preview_image_data: self.preview_image_data.clone(),
+            extra: self.extra.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quartz_nbt::{
+        io::{self, Flavor},
+        NbtCompound, NbtList,
+    };
+
+    use super::{KNOWN_DATA_VERSIONS, MAX_PALETTE_SIZE};
+    use crate::{
+        BlockState, LitematicParseError, LitematicWriteError, Region, RegionNameError,
+        RegionParseError, SaveOptions, Schematic, ValidationWarning, Vector3, Volume,
+    };
+
+    #[test]
+    fn test_isolate_region() {
+        let mut schematic = Schematic::new(
+            Some("bruh".to_string()),
+            Some("yeet".to_string()),
+            None,
+            Some(1234),
+        );
+
+        let mut region1 = Region::new();
+        region1.set_block(Vector3::new(0, 0, 0), "stone");
+        region1.set_block(Vector3::new(1, 2, 3), "basalt");
+
+        let mut region2 = Region::new();
+        region2.set_block(Vector3::new(5, 5, 5), "dirt");
+
+        schematic.regions.insert("region1".to_string(), region1);
+        schematic.regions.insert("region2".to_string(), region2);
+
+        let isolated = schematic.isolate_region("region1").unwrap();
+
+        assert_eq!(isolated.regions.len(), 1);
+        assert_eq!(isolated.name, "bruh");
+        assert_eq!(isolated.author, "yeet");
+        assert_eq!(isolated.time_created, 1234);
+        assert_eq!(isolated.regions["region1"].blocks().len(), 2);
+
+        let parsed = Schematic::from_buffer(&mut isolated.to_buffer().unwrap().as_slice()).unwrap();
+
+        assert_eq!(parsed.regions.len(), 1);
+        assert_eq!(parsed.name, "bruh");
+        assert_eq!(
+            parsed.regions["region1"]
+                .blocks()
+                .get(&Vector3::new(1, 2, 3)),
+            Some(&"basalt".into())
+        );
+
+        assert!(schematic.isolate_region("region3").is_none());
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+        let mut region1 = Region::new();
+        region1.set_block(Vector3::new(0, 0, 0), "stone");
+
+        let mut region2 = Region::new();
+        region2.set_block(Vector3::new(1, 1, 1), "dirt");
+
+        schematic.regions.insert("region1".to_string(), region1);
+        schematic.regions.insert("region2".to_string(), region2);
+
+        let mut clone = schematic.clone();
+
+        clone.name = "yeet".to_string();
+        clone
+            .regions
+            .get_mut("region1")
+            .unwrap()
+            .set_block(Vector3::new(0, 0, 0), "basalt");
+        clone.regions.remove("region2");
+
+        assert_eq!(schematic.name, "bruh");
+        assert_eq!(schematic.regions.len(), 2);
+        assert_eq!(
+            schematic.regions["region1"].blocks()[&Vector3::new(0, 0, 0)],
+            "stone"
+        );
+
+        assert_eq!(clone.regions.len(), 1);
+        assert_eq!(
+            clone.regions["region1"].blocks()[&Vector3::new(0, 0, 0)],
+            "basalt"
+        );
+    }
+
+    #[test]
+    fn test_extra_tags() {
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+        let mut region = Region::new();
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+
+        schematic.regions.insert("main".to_string(), region);
+
+        let buffer = schematic.to_buffer().unwrap();
+
+        let mut nbt = io::read_nbt(&mut buffer.as_slice(), Flavor::GzCompressed)
+            .unwrap()
+            .0;
+
+        let mut regions = nbt.get::<_, &NbtCompound>("Regions").unwrap().clone();
+        let mut main = regions.get::<_, &NbtCompound>("main").unwrap().clone();
+
+        main.insert("ModRegionData", "yeet");
+        regions.insert("main", main);
+        nbt.insert("Regions", regions);
+        nbt.insert("SubVersion", 1);
+
+        let mut edited = Vec::new();
+        io::write_nbt(&mut edited, None, &nbt, Flavor::GzCompressed).unwrap();
+
+        let mut read = Schematic::from_buffer(&mut edited.as_slice()).unwrap();
+
+        assert_eq!(read.extra.get::<_, i32>("SubVersion").unwrap(), 1);
+        assert!(read.extra.get::<_, &NbtCompound>("Metadata").is_err());
+        assert_eq!(
+            read.regions["main"]
+                .extra
+                .get::<_, &String>("ModRegionData")
+                .unwrap(),
+            "yeet"
+        );
+
+        read.regions
+            .get_mut("main")
+            .unwrap()
+            .set_block(Vector3::new(1, 0, 0), "dirt");
+
+        let buffer = read.to_buffer().unwrap();
+        let nbt = io::read_nbt(&mut buffer.as_slice(), Flavor::GzCompressed)
+            .unwrap()
+            .0;
+
+        assert_eq!(nbt.get::<_, i32>("SubVersion").unwrap(), 1);
+        assert_eq!(nbt.get::<_, i32>("Version").unwrap(), 5);
+
+        let main = nbt
+            .get::<_, &NbtCompound>("Regions")
+            .unwrap()
+            .get::<_, &NbtCompound>("main")
+            .unwrap();
+
+        assert_eq!(main.get::<_, &String>("ModRegionData").unwrap(), "yeet");
+        assert_eq!(
+            main.get::<_, &NbtList>("BlockStatePalette").unwrap().len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_preview_image_data() {
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+        let buffer = schematic.to_buffer().unwrap();
+        let read = Schematic::from_buffer(&mut buffer.as_slice()).unwrap();
+
+        assert_eq!(read.preview_image_data, None);
+
+        let preview = vec![0x12345678, -1, 0, i32::MIN];
+        schematic.preview_image_data = Some(preview.clone());
+
+        let buffer = schematic.to_buffer().unwrap();
+        let read = Schematic::from_buffer(&mut buffer.as_slice()).unwrap();
+
+        assert_eq!(read.preview_image_data, Some(preview));
+        assert_eq!(
+            read.preview_image_pixels().unwrap(),
+            vec![
+                [0x12, 0x34, 0x56, 0x78],
+                [0xff, 0xff, 0xff, 0xff],
+                [0, 0, 0, 0],
+                [0x80, 0, 0, 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flavors() {
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+        let mut region = Region::new();
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 2, 3), "dirt");
+
+        schematic.regions.insert("main".to_string(), region);
+
+        for flavor in [
+            Flavor::Uncompressed,
+            Flavor::ZlibCompressed,
+            Flavor::GzCompressed,
+        ] {
+            let buffer = schematic.to_buffer_with_flavor(flavor).unwrap();
+            let read = Schematic::from_buffer_with_flavor(&mut buffer.as_slice(), flavor).unwrap();
+
+            assert_eq!(read.name, "bruh");
+            assert!(read.regions["main"] == schematic.regions["main"]);
+        }
+
+        let uncompressed = schematic
+            .to_buffer_with_flavor(Flavor::Uncompressed)
+            .unwrap();
+
+        assert!(Schematic::from_buffer_with_flavor(
+            &mut uncompressed.as_slice(),
+            Flavor::GzCompressed
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let buffer = {
+            let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+            schematic
+                .regions
+                .insert("region1".to_string(), Region::new());
+            schematic
+                .regions
+                .insert("region2".to_string(), Region::new());
+
+            schematic.to_buffer().unwrap()
+        };
+
+        let mut schematic = Schematic::from_buffer(&mut buffer.as_slice()).unwrap();
+
+        assert_eq!(schematic.validate(), Ok(()));
+
+        schematic.regions.remove("region2");
+
+        let mut region = Region::new();
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(1, 0, 0), "weird mod:block");
+        region.set_block(
+            Vector3::new(2, 0, 0),
+            BlockState::new("weird mod:block", None).with_property("lit", "true"),
+        );
+        schematic.regions.insert("region1".to_string(), region);
+
+        assert_eq!(
+            schematic.validate(),
+            Err(vec![
+                ValidationWarning::RegionCountMismatch {
+                    stored: 2,
+                    actual: 1
+                },
+                ValidationWarning::MalformedBlockId {
+                    region: "region1".to_string(),
+                    id: "weird mod:block".to_string()
+                }
+            ])
+        );
+
+        let mut large = Region::new();
+
+        for i in 0..=MAX_PALETTE_SIZE as i32 {
+            large.set_block(
+                Vector3::new(i % 256, 0, i / 256),
+                BlockState::new("note_block", None).with_property("id", &i.to_string()),
+            );
+        }
+
+        schematic.regions.insert("region1".to_string(), large);
+        schematic.region_count = None;
+
+        assert_eq!(
+            schematic.validate(),
+            Err(vec![ValidationWarning::LargePalette {
+                region: "region1".to_string(),
+                size: MAX_PALETTE_SIZE + 1
+            }])
+        );
+
+        // A stored volume that's empty doesn't matter, since regions are written with a volume that fits their blocks
+        let mut empty_volume = Region::new();
+        empty_volume.set_block(Vector3::new(3, 0, 0), "stone");
+
+        assert_eq!(empty_volume.volume.volume(), 0);
+
+        schematic
+            .regions
+            .insert("region1".to_string(), empty_volume);
+
+        assert_eq!(schematic.validate(), Ok(()));
+
+        let mut huge = Region::new();
+        huge.set_block(Vector3::new(0, 0, 0), "stone");
+        huge.set_block(Vector3::new(1999, 1999, 1999), "stone");
+
+        schematic.regions.insert("region1".to_string(), huge);
+
+        assert_eq!(
+            schematic.validate(),
+            Err(vec![ValidationWarning::VolumeTooLarge {
+                region: "region1".to_string(),
+                size: Vector3::new(2000, 2000, 2000)
+            }])
+        );
+
+        schematic
+            .regions
+            .get_mut("region1")
+            .unwrap()
+            .remove_block(Vector3::new(1999, 1999, 1999));
+
+        assert_eq!(schematic.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_set_region() {
+        let buffer = {
+            let mut schematic = Schematic::new(None, None, None, None);
+            schematic.set_region("a", Region::new()).unwrap();
+            schematic.to_buffer().unwrap()
+        };
+
+        let mut schematic = Schematic::from_buffer(&mut buffer.as_slice()).unwrap();
+
+        assert_eq!(schematic.region_count, Some(1));
+
+        let mut region = Region::new();
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+
+        assert!(schematic.set_region("b", region.clone()).unwrap().is_none());
+        assert_eq!(schematic.region_count, Some(2));
+        assert_eq!(schematic.validate(), Ok(()));
+
+        let old = schematic.set_region("b", Region::new()).unwrap().unwrap();
+
+        assert!(old == region);
+        assert_eq!(schematic.region_count, Some(2));
+
+        assert!(matches!(
+            schematic.set_region("", region),
+            Err(RegionNameError::Empty)
+        ));
+        assert_eq!(schematic.regions.len(), 2);
+        assert!(!schematic.regions.contains_key(""));
+    }
+
+    #[test]
+    fn test_write_to() {
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+        let mut region = Region::new();
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(3, 1, 2), "dirt");
+
+        schematic.regions.insert("main".to_string(), region);
+
+        let mut written = Vec::new();
+        schematic.write_to(&mut written).unwrap();
+
+        let buffer = schematic.to_buffer().unwrap();
+
+        let read = |data: &[u8]| {
+            io::read_nbt(&mut &data[..], Flavor::GzCompressed)
+                .unwrap()
+                .0
+        };
+
+        assert_eq!(read(&written), read(&buffer));
+
+        let read_back = Schematic::from_buffer(&mut written.as_slice()).unwrap();
+
+        assert!(read_back.regions["main"] == schematic.regions["main"]);
+    }
+
+    #[test]
+    fn test_to_buffer_with_options() {
+        let metadata = |schematic: &Schematic, options: SaveOptions| {
+            let buffer = schematic.to_buffer_with_options(options).unwrap();
+
+            let nbt = io::read_nbt(&mut buffer.as_slice(), Flavor::GzCompressed)
+                .unwrap()
+                .0;
+
+            nbt.get::<_, &NbtCompound>("Metadata").unwrap().clone()
+        };
+
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, Some(1234));
+
+        for name in ["region1", "region2", "region3"].iter() {
+            let mut region = Region::new();
+            region.set_block(Vector3::new(0, 0, 0), "stone");
+
+            schematic.regions.insert(name.to_string(), region);
+        }
+
+        let default = metadata(&schematic, SaveOptions::default());
+
+        assert_eq!(default.get::<_, i64>("TimeModified").unwrap(), 1234);
+        assert_eq!(default.get::<_, i32>("RegionCount").unwrap(), 3);
+
+        let refreshed = metadata(
+            &schematic,
+            SaveOptions {
+                refresh_modified: true,
+                ..SaveOptions::default()
+            },
+        );
+
+        assert!(refreshed.get::<_, i64>("TimeModified").unwrap() > 1234);
+        assert_eq!(refreshed.get::<_, i64>("TimeCreated").unwrap(), 1234);
+
+        let mut parsed =
+            Schematic::from_buffer(&mut schematic.to_buffer().unwrap().as_slice()).unwrap();
+        parsed.regions.remove("region2");
+
+        let kept_count = metadata(
+            &parsed,
+            SaveOptions {
+                recompute_region_count: false,
+                ..SaveOptions::default()
+            },
+        );
+
+        assert_eq!(kept_count.get::<_, i32>("RegionCount").unwrap(), 3);
+        assert_eq!(
+            metadata(&parsed, SaveOptions::default())
+                .get::<_, i32>("RegionCount")
+                .unwrap(),
+            2
+        );
+
+        let names = [
+            "delta", "alpha", "hotel", "charlie", "bravo", "golf", "foxtrot", "echo",
+        ];
+
+        for name in names.iter() {
+            schematic.regions.insert(name.to_string(), Region::new());
+        }
+
+        let buffer = schematic
+            .to_buffer_with_options(SaveOptions {
+                sort_regions: true,
+                ..SaveOptions::default()
+            })
+            .unwrap();
+
+        let nbt = io::read_nbt(&mut buffer.as_slice(), Flavor::GzCompressed)
+            .unwrap()
+            .0;
+
+        let written: Vec<_> = nbt
+            .get::<_, &NbtCompound>("Regions")
+            .unwrap()
+            .inner()
+            .keys()
+            .cloned()
+            .collect();
+
+        let mut expected: Vec<_> = schematic.regions.keys().cloned().collect();
+        expected.sort();
+
+        assert_eq!(written, expected);
+    }
+
+    #[test]
+    fn test_iter_blocks() {
+        let mut schematic = Schematic::new(None, None, None, None);
+
+        let mut region1 = Region::new();
+        region1.volume = Volume::new(Vector3::new(10, 0, 0), Vector3::new(1, 1, 1));
+        region1.set_block(Vector3::new(0, 0, 0), "stone");
+        region1.set_block(Vector3::new(1, 0, 0), "dirt");
+
+        let mut region2 = Region::new();
+        region2.volume = Volume::new(Vector3::new(-5, 2, 0), Vector3::new(1, 1, 1));
+        region2.set_block(Vector3::new(0, 0, 0), "basalt");
+
+        schematic.regions.insert("region1".to_string(), region1);
+        schematic.regions.insert("region2".to_string(), region2);
+
+        let mut blocks: Vec<_> = schematic
+            .iter_blocks()
+            .map(|(pos, block)| (pos, block.to_string()))
+            .collect();
+
+        blocks.sort_by_key(|(pos, _)| pos.x);
+
+        assert_eq!(blocks.len() as u64, schematic.total_blocks());
+        assert_eq!(
+            blocks,
+            vec![
+                (Vector3::new(-5, 2, 0), "minecraft:basalt".to_string()),
+                (Vector3::new(10, 0, 0), "minecraft:stone".to_string()),
+                (Vector3::new(11, 0, 0), "minecraft:dirt".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_block() {
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+        let mut region1 = Region::new();
+        region1.set_block(Vector3::new(0, 0, 0), "stone");
+        region1.set_block(Vector3::new(1, 0, 0), "stone");
+        region1.set_block(Vector3::new(2, 0, 0), "dirt");
+
+        let mut region2 = Region::new();
+        region2.set_block(Vector3::new(0, 0, 0), "stone");
+        region2.set_block(Vector3::new(0, 1, 0), "glass");
+
+        schematic.regions.insert("region1".to_string(), region1);
+        schematic.regions.insert("region2".to_string(), region2);
+
+        let stone = BlockState::new("stone", None);
+
+        assert_eq!(
+            schematic.replace_block(&stone, BlockState::new("deepslate", None)),
+            3
+        );
+        assert_eq!(schematic.regions["region1"].count_of_block("deepslate"), 2);
+        assert_eq!(schematic.regions["region2"].count_of_block("deepslate"), 1);
+        assert_eq!(schematic.regions["region1"].count_of_block("stone"), 0);
+        assert!(schematic.time_modified > 0);
+
+        assert_eq!(
+            schematic.replace_block(&stone, BlockState::new("dirt", None)),
+            0
+        );
+        assert_eq!(
+            schematic.replace_block(
+                &BlockState::new("glass", None),
+                BlockState::new("air", None)
+            ),
+            1
+        );
+        assert_eq!(schematic.regions["region2"].blocks().len(), 1);
+        assert_eq!(schematic.total_blocks(), 4);
+    }
+
+    #[test]
+    fn test_palette() {
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+        let mut region1 = Region::new();
+        region1.set_block(Vector3::new(0, 0, 0), "stone");
+        region1.set_block(Vector3::new(1, 0, 0), "glass");
+
+        let mut region2 = Region::new();
+        region2.set_block(Vector3::new(0, 0, 0), "stone");
+        region2.set_block(Vector3::new(0, 1, 0), "dirt");
+
+        schematic.regions.insert("region1".to_string(), region1);
+        schematic.regions.insert("region2".to_string(), region2);
+
+        assert_eq!(
+            schematic.palette(),
+            vec![
+                BlockState::new("dirt", None),
+                BlockState::new("glass", None),
+                BlockState::new("stone", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut schematic = Schematic::new(
+            Some("library".to_string()),
+            Some("yeet".to_string()),
+            None,
+            Some(1234),
+        );
+
+        let mut house = Region::new();
+        house.set_block(Vector3::new(0, 0, 0), "oak_planks");
+
+        schematic.regions.insert("house".to_string(), house.clone());
+        schematic
+            .regions
+            .insert("new house".to_string(), house.clone());
+
+        let mut other = Schematic::new(Some("other".to_string()), None, None, None);
+
+        let mut other_house = Region::new();
+        other_house.set_block(Vector3::new(0, 0, 0), "stone");
+
+        other.regions.insert("house".to_string(), other_house);
+        other.regions.insert("farm".to_string(), Region::new());
+
+        schematic.merge(other, "new ");
+
+        assert_eq!(schematic.regions.len(), 4);
+        assert_eq!(schematic.name, "library");
+        assert_eq!(schematic.author, "yeet");
+        assert!(schematic.time_modified > 1234);
+        assert!(schematic.regions.contains_key("farm"));
+        assert_eq!(
+            schematic.regions["house"].blocks()[&Vector3::new(0, 0, 0)],
+            "oak_planks"
+        );
+        assert_eq!(
+            schematic.regions["new house 2"].blocks()[&Vector3::new(0, 0, 0)],
+            "stone"
+        );
+
+        let mut parsed =
+            Schematic::from_buffer(&mut schematic.to_buffer().unwrap().as_slice()).unwrap();
+
+        parsed.merge(schematic, "");
+
+        assert_eq!(parsed.regions.len(), 8);
+        assert_eq!(parsed.region_count, Some(8));
+        assert!(parsed.regions.contains_key("new house 2 2"));
+    }
+
+    #[test]
+    fn test_from_schem_buffer() {
+        let mut root = NbtCompound::new();
+
+        root.insert("Version", 2);
+        root.insert("DataVersion", 2586);
+        root.insert("Width", 2_i16);
+        root.insert("Height", 1_i16);
+        root.insert("Length", 2_i16);
+        root.insert("Offset", vec![1, 2, 3]);
+
+        let mut palette = NbtCompound::new();
+
+        palette.insert("minecraft:air", 0);
+        palette.insert("minecraft:stone", 1);
+        palette.insert("minecraft:observer[facing=west]", 2);
+
+        root.insert("Palette", palette);
+        root.insert("BlockData", vec![1_i8, 0, 0, 2]);
+
+        let mut metadata = NbtCompound::new();
+
+        metadata.insert("Name", "bruh");
+
+        root.insert("Metadata", metadata);
+
+        let mut buffer = Vec::new();
+
+        io::write_nbt(&mut buffer, Some("Schematic"), &root, Flavor::GzCompressed).unwrap();
+
+        let schematic = Schematic::from_schem_buffer(&mut buffer.as_slice()).unwrap();
+
+        assert_eq!(schematic.name, "bruh");
+        assert_eq!(schematic.data_version, 2586);
+
+        let region = &schematic.regions["bruh"];
+
+        assert_eq!(region.volume.origin(), Vector3::new(1, 2, 3));
+        assert_eq!(region.volume.size(), Vector3::new(2, 1, 2));
+        assert_eq!(region.blocks().len(), 2);
+        assert_eq!(
+            region.blocks().get(&Vector3::new(0, 0, 0)),
+            Some(&BlockState::new("stone", None))
+        );
+        assert_eq!(
+            region.blocks().get(&Vector3::new(1, 0, 1)),
+            Some(&"observer[facing=west]".parse().unwrap())
+        );
+
+        root.insert("BlockData", vec![1_i8, 0, 0]);
+
+        let mut buffer = Vec::new();
+
+        io::write_nbt(&mut buffer, Some("Schematic"), &root, Flavor::GzCompressed).unwrap();
+
+        assert!(Schematic::from_schem_buffer(&mut buffer.as_slice()).is_err());
+
+        // Sizes of 65535 along every axis hold more blocks than fit in an i32
+        root.insert("Width", -1_i16);
+        root.insert("Height", -1_i16);
+        root.insert("Length", -1_i16);
+
+        let mut buffer = Vec::new();
+
+        io::write_nbt(&mut buffer, Some("Schematic"), &root, Flavor::GzCompressed).unwrap();
+
+        assert!(matches!(
+            Schematic::from_schem_buffer(&mut buffer.as_slice()),
+            Err(LitematicParseError::RegionParseError {
+                error: RegionParseError::InvalidDimensions(_),
+                ..
+            })
+        ));
+
+        root.insert("Width", 2_i16);
+        root.insert("Height", 1_i16);
+        root.insert("Length", 2_i16);
+        root.insert("Offset", vec![i32::MAX, 0, 0]);
+
+        let mut buffer = Vec::new();
+
+        io::write_nbt(&mut buffer, Some("Schematic"), &root, Flavor::GzCompressed).unwrap();
+
+        assert!(matches!(
+            Schematic::from_schem_buffer(&mut buffer.as_slice()),
+            Err(LitematicParseError::RegionParseError {
+                error: RegionParseError::InvalidDimensions(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_to_schem_buffer_too_large() {
+        let mut schematic = Schematic::new(None, None, None, None);
+
+        let mut region = Region::new();
+        region.set_block(Vector3::new(0, 0, 0), "stone");
+        region.set_block(Vector3::new(70_000, 0, 0), "stone");
+
+        schematic.regions.insert("main".to_string(), region);
+
+        assert!(matches!(
+            schematic.to_schem_buffer(),
+            Err(LitematicWriteError::TooLargeForSchem(size)) if size == Vector3::new(70_001, 1, 1)
+        ));
+    }
+
+    #[test]
+    fn test_to_schem_buffer() {
+        let mut schematic = Schematic::new(Some("bruh".to_string()), None, None, None);
+
+        let mut region1 = Region::new();
+
+        region1.volume = Volume::new(Vector3::new(-1, 0, 0), Vector3::new(1, 1, 1));
+        region1.set_block(Vector3::new(0, 0, 0), "stone");
+        region1.set_block(Vector3::new(1, 0, 0), "dirt");
+
+        let mut region2 = Region::new();
+
+        region2.volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(1, 1, 1));
+        region2.set_block(
+            Vector3::new(0, 0, 0),
+            "observer[facing=west]".parse::<BlockState>().unwrap(),
+        );
+        region2.set_block(Vector3::new(0, 2, 1), "basalt");
+
+        schematic.regions.insert("a".to_string(), region1);
+        schematic.regions.insert("b".to_string(), region2);
+
+        let buffer = schematic.to_schem_buffer().unwrap();
+
+        let parsed = Schematic::from_schem_buffer(&mut buffer.as_slice()).unwrap();
+
+        let region = &parsed.regions["bruh"];
+
+        assert_eq!(region.volume.origin(), Vector3::new(-1, 0, 0));
+        assert_eq!(region.volume.size(), Vector3::new(2, 3, 2));
+        assert_eq!(region.blocks().len(), 3);
+        assert_eq!(
+            region.blocks().get(&Vector3::new(0, 0, 0)),
+            Some(&BlockState::new("stone", None))
+        );
+        assert_eq!(
+            region.blocks().get(&Vector3::new(1, 0, 0)),
+            Some(&"observer[facing=west]".parse().unwrap())
+        );
+        assert_eq!(
+            region.blocks().get(&Vector3::new(1, 2, 1)),
+            Some(&BlockState::new("basalt", None))
+        );
+
+        assert_eq!(schematic.to_schem_buffer().unwrap(), buffer);
+
+        let nbt = io::read_nbt(&mut buffer.as_slice(), Flavor::GzCompressed)
+            .unwrap()
+            .0;
+        let palette = nbt.get::<_, &NbtCompound>("Palette").unwrap();
+
+        // Written in the order of the indices, which follow the order the blocks first show up in
+        let names: Vec<_> = palette.inner().keys().cloned().collect();
+        let indices: Vec<_> = names
+            .iter()
+            .map(|name| palette.get::<_, i32>(name.as_str()).unwrap())
+            .collect();
+
+        assert_eq!(names[..2], ["minecraft:air", "minecraft:stone"]);
+        assert_eq!(indices, (0..names.len() as i32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_minecraft_version_name() {
+        let mut schematic = Schematic::new(None, None, None, None);
+
+        schematic.data_version = 2975;
+        assert_eq!(schematic.minecraft_version_name(), Some("1.18.2"));
+
+        schematic.data_version = 3465;
+        assert_eq!(schematic.minecraft_version_name(), Some("1.20.1"));
+
+        schematic.data_version = 2976;
+        assert_eq!(schematic.minecraft_version_name(), None);
+    }
+
+    #[test]
+    fn test_data_version_constants() {
+        let mut schematic = Schematic::new(None, None, None, None);
+
+        for data_version in [
+            Schematic::DATA_VERSION_1_12_2,
+            Schematic::DATA_VERSION_1_13_2,
+            Schematic::DATA_VERSION_1_14_4,
+            Schematic::DATA_VERSION_1_15_2,
+            Schematic::DATA_VERSION_1_16_5,
+            Schematic::DATA_VERSION_1_17_1,
+            Schematic::DATA_VERSION_1_18_2,
+            Schematic::DATA_VERSION_1_19_4,
+            Schematic::DATA_VERSION_1_20_1,
+            Schematic::DATA_VERSION_1_20_4,
+            Schematic::DATA_VERSION_1_20_6,
+            Schematic::DATA_VERSION_1_21_1,
+            Schematic::DATA_VERSION_1_21_4,
+        ] {
+            schematic.set_data_version(data_version);
+
+            let name = schematic.minecraft_version_name().unwrap();
+
+            assert_eq!(schematic.set_minecraft_version(name), Some(data_version));
+            assert!(data_version >= Schematic::MINIMUM_DATA_VERSION);
+        }
+
+        assert_eq!(
+            KNOWN_DATA_VERSIONS.iter().map(|(v, _)| *v).min(),
+            Some(Schematic::MINIMUM_DATA_VERSION)
+        );
+    }
+
+    #[test]
+    fn test_to_structure_nbt() {
+        let mut schematic = Schematic::new(None, None, None, None);
+
+        let mut region1 = Region::new();
+        region1.set_block(Vector3::new(0, 0, 0), "stone");
+
+        let mut region2 = Region::new();
+        region2.volume = Volume::new(Vector3::new(3, 0, 0), Vector3::new(1, 1, 1));
+        region2.set_block(Vector3::new(0, 1, 0), "dirt");
+
+        schematic.regions.insert("region1".to_string(), region1);
+        schematic.regions.insert("region2".to_string(), region2);
+
+        let merged = schematic.to_structure_nbt(None).unwrap();
+
+        assert_eq!(merged.get::<_, i32>("DataVersion").unwrap(), 2730);
+        assert_eq!(merged.get::<_, &NbtList>("blocks").unwrap().len(), 2);
+
+        assert_eq!(
+            Vector3::from_nbt_list(merged.get::<_, &NbtList>("size").unwrap()),
+            Some(Vector3::new(4, 2, 1))
+        );
+
+        let single = schematic.to_structure_nbt(Some("region2")).unwrap();
+
+        assert_eq!(single.get::<_, &NbtList>("blocks").unwrap().len(), 1);
     }
 }
@@ -1,10 +1,10 @@
 use std::{
     convert::TryFrom,
     error::Error,
-    ops::{Add, Sub},
+    ops::{Add, AddAssign, Mul, Sub, SubAssign},
 };
 
-use quartz_nbt::{NbtCompound, NbtReprError, NbtTag};
+use quartz_nbt::{NbtCompound, NbtList, NbtReprError, NbtTag};
 
 /// A Vector3 of i32 values
 pub type IVector3 = Vector3<i32>;
@@ -41,6 +41,95 @@ impl<T: Copy> Vector3<T> {
     }
 }
 
+impl<T: Copy + From<u8>> Vector3<T> {
+    /// Create a vector where all three values are zero
+    ///
+    /// ```
+    /// # use litematic_editor::{FVector3, IVector3, UVector3, Vector3};
+    /// assert_eq!(IVector3::zero(), Vector3::new(0, 0, 0));
+    /// assert_eq!(UVector3::zero(), Vector3::new(0, 0, 0));
+    /// assert_eq!(FVector3::zero(), Vector3::new(0.0, 0.0, 0.0));
+    /// ```
+    pub fn zero() -> Vector3<T> {
+        Vector3::new(T::from(0), T::from(0), T::from(0))
+    }
+
+    /// Create a vector where all three values are one
+    ///
+    /// ```
+    /// # use litematic_editor::{FVector3, IVector3, UVector3, Vector3};
+    /// assert_eq!(IVector3::one(), Vector3::new(1, 1, 1));
+    /// assert_eq!(UVector3::one(), Vector3::new(1, 1, 1));
+    /// assert_eq!(FVector3::one(), Vector3::new(1.0, 1.0, 1.0));
+    /// ```
+    pub fn one() -> Vector3<T> {
+        Vector3::new(T::from(1), T::from(1), T::from(1))
+    }
+}
+
+impl<T: Copy + Ord> Vector3<T> {
+    /// Get the lowest of each of the values of the two vectors
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(-1, 5, 0).min(Vector3::new(3, -2, 0)), Vector3::new(-1, -2, 0));
+    /// ```
+    pub fn min(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Get the highest of each of the values of the two vectors
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(-1, 5, 0).max(Vector3::new(3, -2, 0)), Vector3::new(3, 5, 0));
+    /// ```
+    pub fn max(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// Restrict each of the values of the vector to be between the values of `low` and `high`, inclusive
+    ///
+    /// Panics if any value of `low` is greater than the value of `high`, like [Ord::clamp()]
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// let low = Vector3::new(-4, -4, 0);
+    /// let high = Vector3::new(4, 4, 10);
+    ///
+    /// assert_eq!(Vector3::new(-10, 2, 15).clamp(low, high), Vector3::new(-4, 2, 10));
+    /// assert_eq!(Vector3::new(5, -5, -1).clamp(low, high), Vector3::new(4, -4, 0));
+    /// ```
+    pub fn clamp(self, low: Vector3<T>, high: Vector3<T>) -> Vector3<T> {
+        Vector3::new(
+            self.x.clamp(low.x, high.x),
+            self.y.clamp(low.y, high.y),
+            self.z.clamp(low.z, high.z),
+        )
+    }
+}
+
+impl<T: Copy + Mul<Output = T> + Add<Output = T>> Vector3<T> {
+    /// Get the dot product of this vector and another vector
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(1, 2, 3).dot(Vector3::new(4, -5, 6)), 12);
+    /// assert_eq!(Vector3::new(1.0, 0.0, 0.0).dot(Vector3::new(0.0, 1.0, 0.0)), 0.0);
+    /// ```
+    pub fn dot(self, other: Vector3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
 impl<'a, T> Vector3<T>
 where
     T: Copy + TryFrom<&'a NbtTag>,
@@ -49,10 +138,15 @@ where
     pub(crate) fn from_nbt(nbt: &'a NbtCompound, name: &str) -> Result<Vector3<T>, NbtReprError> {
         let vec_nbt = nbt.get::<_, &NbtCompound>(name)?;
 
+        Vector3::from_xyz_nbt(vec_nbt)
+    }
+
+    /// Read a vector out of the `x`, `y`, and `z` tags of a compound, the way block entities store their positions
+    pub(crate) fn from_xyz_nbt(nbt: &'a NbtCompound) -> Result<Vector3<T>, NbtReprError> {
         Ok(Vector3::new(
-            vec_nbt.get::<'a, 'a, str, T>("x")?,
-            vec_nbt.get::<'a, 'a, str, T>("y")?,
-            vec_nbt.get::<'a, 'a, str, T>("z")?,
+            nbt.get::<'a, 'a, str, T>("x")?,
+            nbt.get::<'a, 'a, str, T>("y")?,
+            nbt.get::<'a, 'a, str, T>("z")?,
         ))
     }
 }
@@ -97,6 +191,108 @@ impl Vector3<i32> {
     pub fn volume(self) -> i32 {
         (self.x * self.y * self.z).abs()
     }
+
+    /// Like [volume()](Vector3::volume), but returns `None` instead of overflowing when the volume doesn't fit in an `i32`
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(-3, 2, 7).checked_volume(), Some(42));
+    /// assert_eq!(Vector3::new(2000, 2000, 2000).checked_volume(), None);
+    /// ```
+    pub fn checked_volume(self) -> Option<i32> {
+        self.x
+            .checked_mul(self.y)?
+            .checked_mul(self.z)?
+            .checked_abs()
+    }
+
+    /// Get the cross product of this vector and another vector, which is perpendicular to both of them
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(1, 0, 0).cross(Vector3::new(0, 1, 0)), Vector3::new(0, 0, 1));
+    /// assert_eq!(Vector3::new(1, 2, 3).cross(Vector3::new(4, 5, 6)), Vector3::new(-3, 6, -3));
+    /// ```
+    pub fn cross(self, other: Vector3<i32>) -> Vector3<i32> {
+        Vector3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Get the squared length of this vector
+    ///
+    /// Returned as an `i64` since the squared length of a vector overflows an `i32` once its coordinates get past 46,340, or past 26,754 when all three are that large. Take the square root of the result as an `f64` if the actual length is needed
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(2, -3, 6).length_squared(), 49);
+    /// assert_eq!(Vector3::new(i32::MAX, 0, 0).length_squared(), 4_611_686_014_132_420_609);
+    /// ```
+    pub fn length_squared(self) -> i64 {
+        let [x, y, z] = [self.x as i64, self.y as i64, self.z as i64];
+
+        x * x + y * y + z * z
+    }
+
+    /// Convert this vector into an array of three f32 values
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(1, -2, 3).to_f32_array(), [1.0, -2.0, 3.0]);
+    /// ```
+    pub fn to_f32_array(self) -> [f32; 3] {
+        [self.x as f32, self.y as f32, self.z as f32]
+    }
+}
+
+impl Vector3<f32> {
+    /// Get the squared length of this vector, which is cheaper than [length()](Vector3::length) for comparing lengths
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(2.0, -3.0, 6.0).length_squared(), 49.0);
+    /// ```
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    /// Get the length of this vector
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(2.0, -3.0, 6.0).length(), 7.0);
+    /// assert_eq!(Vector3::new(0.0, 0.0, 0.0).length(), 0.0);
+    /// ```
+    pub fn length(self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    /// Get the cross product of this vector and another vector, which is perpendicular to both of them
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(0.0, 1.0, 0.0).cross(Vector3::new(0.0, 0.0, 1.0)), Vector3::new(1.0, 0.0, 0.0));
+    /// assert_eq!(Vector3::new(1.0, 2.0, 3.0).cross(Vector3::new(4.0, 5.0, 6.0)), Vector3::new(-3.0, 6.0, -3.0));
+    /// ```
+    pub fn cross(self, other: Vector3<f32>) -> Vector3<f32> {
+        Vector3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Convert this vector into an array of three values
+    ///
+    /// ```
+    /// # use litematic_editor::Vector3;
+    /// assert_eq!(Vector3::new(1.5, -2.0, 0.25).to_array(), [1.5, -2.0, 0.25]);
+    /// ```
+    pub fn to_array(self) -> [f32; 3] {
+        self.into_slice()
+    }
 }
 
 impl<T: Copy + Add<Output = T>> Add for Vector3<T> {
@@ -127,6 +323,18 @@ impl<T: Copy + Sub<Output = T>> Sub for Vector3<T> {
     }
 }
 
+impl<T: Copy + Add<Output = T>> AddAssign for Vector3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Vector3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
 impl<T: Copy + Into<NbtTag>> Into<NbtTag> for Vector3<T> {
     fn into(self) -> NbtTag {
         let mut compound = NbtCompound::new();
@@ -139,6 +347,40 @@ impl<T: Copy + Into<NbtTag>> Into<NbtTag> for Vector3<T> {
     }
 }
 
+impl Vector3<i32> {
+    /// Read a vector out of a list of three ints, the way vanilla structures store positions
+    pub(crate) fn from_nbt_list(list: &NbtList) -> Option<Vector3<i32>> {
+        let mut values = Vec::new();
+
+        for tag in list {
+            if let NbtTag::Int(value) = tag {
+                values.push(*value);
+            } else {
+                return None;
+            }
+        }
+
+        if values.len() != 3 {
+            return None;
+        }
+
+        Some(Vector3::new(values[0], values[1], values[2]))
+    }
+}
+
+impl<T: Copy + Into<NbtTag>> Vector3<T> {
+    /// Convert a vector into a list of three values, the way vanilla structures store positions
+    pub(crate) fn to_nbt_list(self) -> NbtList {
+        let mut list = NbtList::new();
+
+        list.push(self.x);
+        list.push(self.y);
+        list.push(self.z);
+
+        list
+    }
+}
+
 impl Default for Vector3<i32> {
     fn default() -> Self {
         Vector3 { x: 0, y: 0, z: 0 }
@@ -156,6 +398,21 @@ mod test {
         assert_eq!(Vector3::new(-2, 3, 4).volume(), 24);
     }
 
+    #[test]
+    fn test_assign_ops() {
+        let mut pos = Vector3::new(1, 2, 3);
+
+        pos += Vector3::new(1, -1, 2);
+        assert_eq!(pos, Vector3::new(2, 1, 5));
+
+        pos -= Vector3::new(3, 1, 1);
+        assert_eq!(pos, Vector3::new(-1, 0, 4));
+
+        let mut offset = Vector3::new(0.5, 0.0, 1.0);
+        offset += Vector3::new(0.5, 1.5, -1.0);
+        assert_eq!(offset, Vector3::new(1.0, 1.5, 0.0));
+    }
+
     #[test]
     fn test_from_nbt() {
         let mut root = NbtCompound::new();
@@ -174,6 +431,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_nbt_list() {
+        let list = Vector3::new(2, -3, 4).to_nbt_list();
+
+        assert_eq!(Vector3::from_nbt_list(&list), Some(Vector3::new(2, -3, 4)));
+
+        let mut too_short = NbtList::new();
+
+        too_short.push(1);
+        too_short.push(2);
+
+        assert_eq!(Vector3::from_nbt_list(&too_short), None);
+        assert_eq!(
+            Vector3::from_nbt_list(&Vector3::new(1.0, 2.0, 3.0).to_nbt_list()),
+            None
+        );
+    }
+
     #[test]
     fn test_to_nbt() {
         let nbt: NbtTag = Vector3::new(2, 3, 4).into();
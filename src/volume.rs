@@ -18,6 +18,31 @@ impl Volume {
         }
     }
 
+    /// Create a volume spanning two opposite corners, which are both included in the volume
+    ///
+    /// The corners can be given in any order, and the size of the volume returned is always positive, so the size along each axis is the distance between the corners plus one
+    ///
+    /// ```
+    /// # use litematic_editor::{Volume, Vector3};
+    /// let volume = Volume::from_corners(Vector3::new(0, 0, 0), Vector3::new(2, 3, 4));
+    ///
+    /// assert_eq!(volume, Volume::new(Vector3::new(0, 0, 0), Vector3::new(3, 4, 5)));
+    /// assert!(volume.contains(Vector3::new(2, 3, 4)));
+    ///
+    /// assert_eq!(Volume::from_corners(Vector3::new(2, 3, 4), Vector3::new(0, 0, 0)), volume);
+    /// assert_eq!(
+    ///     Volume::from_corners(Vector3::new(5, -1, 0), Vector3::new(1, 1, 0)),
+    ///     Volume::new(Vector3::new(1, -1, 0), Vector3::new(5, 3, 1))
+    /// );
+    /// assert_eq!(Volume::from_corners(Vector3::new(1, 1, 1), Vector3::new(1, 1, 1)).volume(), 1);
+    /// ```
+    pub fn from_corners(a: Vector3<i32>, b: Vector3<i32>) -> Volume {
+        Volume {
+            pos1: a.min(b),
+            pos2: a.max(b) + Vector3::new(1, 1, 1),
+        }
+    }
+
     /// Change the position and size of this volume so that it'll contain the volume given
     ///
     /// Favors changing the size over changing the position
@@ -36,6 +61,132 @@ impl Volume {
             .expand_to_fit(volume_positive.pos2 - Vector3::new(1, 1, 1))
     }
 
+    /// Get the smallest volume containing both this volume and the volume given
+    ///
+    /// Unlike [expand_to_fit_volume()](Volume::expand_to_fit_volume), the order of the volumes doesn't matter, and the size of the volume returned is always positive
+    ///
+    /// ```
+    /// # use litematic_editor::{Volume, Vector3};
+    /// let volume1 = Volume::new(Vector3::new(0, 0, 0), Vector3::new(2, 2, 2));
+    /// let volume2 = Volume::new(Vector3::new(5, 1, -1), Vector3::new(-1, 3, 1));
+    ///
+    /// let union = volume1.union(volume2);
+    ///
+    /// assert_eq!(union, Volume::new(Vector3::new(0, 0, -1), Vector3::new(5, 4, 3)));
+    /// assert_eq!(union, volume2.union(volume1));
+    ///
+    /// assert!(union.contains(Vector3::new(0, 0, 0)));
+    /// assert!(union.contains(Vector3::new(1, 1, 1)));
+    /// assert!(union.contains(Vector3::new(4, 3, -1)));
+    /// assert!(!union.contains(Vector3::new(5, 3, -1)));
+    /// ```
+    pub fn union(self, other: Volume) -> Volume {
+        let volume1 = self.make_size_positive();
+        let volume2 = other.make_size_positive();
+
+        Volume {
+            pos1: volume1.pos1.min(volume2.pos1),
+            pos2: volume1.pos2.max(volume2.pos2),
+        }
+    }
+
+    /// Crop this volume so it fits inside of the bounds given, for example to keep an edit from going outside of a region
+    ///
+    /// Returns `None` if none of this volume is inside of the bounds. The size of the volume returned is always positive
+    ///
+    /// ```
+    /// # use litematic_editor::{Volume, Vector3};
+    /// let bounds = Volume::new(Vector3::new(0, 0, 0), Vector3::new(10, 10, 10));
+    ///
+    /// let edit = Volume::new(Vector3::new(8, -2, 3), Vector3::new(4, 4, 2));
+    /// assert_eq!(edit.clamp(bounds), Some(Volume::new(Vector3::new(8, 0, 3), Vector3::new(2, 2, 2))));
+    ///
+    /// let inside = Volume::new(Vector3::new(1, 1, 1), Vector3::new(2, 2, 2));
+    /// assert_eq!(inside.clamp(bounds), Some(inside));
+    ///
+    /// let outside = Volume::new(Vector3::new(10, 0, 0), Vector3::new(2, 2, 2));
+    /// assert_eq!(outside.clamp(bounds), None);
+    /// ```
+    pub fn clamp(self, bounds: Volume) -> Option<Volume> {
+        let volume = self.make_size_positive();
+        let bounds = bounds.make_size_positive();
+
+        let pos1 = volume.pos1.max(bounds.pos1);
+        let pos2 = volume.pos2.min(bounds.pos2);
+
+        if pos1.x >= pos2.x || pos1.y >= pos2.y || pos1.z >= pos2.z {
+            return None;
+        }
+
+        Some(Volume { pos1, pos2 })
+    }
+
+    /// Split the volume into pieces of the chunk size given, covering the whole volume, for example to process a build in 16x16x16 chunk sections
+    ///
+    /// The volume's size is made positive first, and the pieces start at its origin, so the pieces at the far edges can be smaller than the chunk size. The pieces are in the same order as [iter()](Volume::iter) goes through positions
+    ///
+    /// Panics if any value of `chunk` isn't positive
+    ///
+    /// ```
+    /// # use litematic_editor::{Volume, Vector3};
+    /// let volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(20, 20, 20));
+    ///
+    /// let pieces = volume.split(Vector3::new(16, 16, 16));
+    ///
+    /// assert_eq!(pieces.len(), 8);
+    /// assert_eq!(pieces[0], Volume::new(Vector3::new(0, 0, 0), Vector3::new(16, 16, 16)));
+    /// assert_eq!(pieces[1], Volume::new(Vector3::new(16, 0, 0), Vector3::new(4, 16, 16)));
+    /// assert_eq!(pieces[7], Volume::new(Vector3::new(16, 16, 16), Vector3::new(4, 4, 4)));
+    /// assert_eq!(pieces.iter().map(|v| v.volume()).sum::<i32>(), volume.volume());
+    /// ```
+    pub fn split(self, chunk: Vector3<i32>) -> Vec<Volume> {
+        assert!(
+            chunk.x > 0 && chunk.y > 0 && chunk.z > 0,
+            "The chunk size must be positive"
+        );
+
+        let volume = self.make_size_positive();
+        let mut pieces = Vec::new();
+
+        for y in (volume.pos1.y..volume.pos2.y).step_by(chunk.y as usize) {
+            for z in (volume.pos1.z..volume.pos2.z).step_by(chunk.z as usize) {
+                for x in (volume.pos1.x..volume.pos2.x).step_by(chunk.x as usize) {
+                    let pos1 = Vector3::new(x, y, z);
+
+                    pieces.push(Volume {
+                        pos1,
+                        pos2: (pos1 + chunk).min(volume.pos2),
+                    });
+                }
+            }
+        }
+
+        pieces
+    }
+
+    /// Grow the volume by the margin given on every side, or shrink it if the margin is negative
+    ///
+    /// The volume's size is made positive first, then its origin is moved by `-margin` and its size grows by `2 * margin`
+    ///
+    /// A negative margin more than half the size along an axis makes the size along that axis zero or negative. Since [contains()](Volume::contains) and [iter()](Volume::iter) flip negative sizes, check the size of the volume returned before using it when shrinking
+    ///
+    /// ```
+    /// # use litematic_editor::{Volume, Vector3};
+    /// let volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(4, 4, 4));
+    ///
+    /// assert_eq!(volume.expanded(Vector3::new(2, 2, 2)), Volume::new(Vector3::new(-2, -2, -2), Vector3::new(8, 8, 8)));
+    /// assert_eq!(volume.expanded(Vector3::new(1, 0, -1)), Volume::new(Vector3::new(-1, 0, 1), Vector3::new(6, 4, 2)));
+    /// assert_eq!(volume.expanded(Vector3::new(0, -3, 0)).size(), Vector3::new(4, -2, 4));
+    /// ```
+    pub fn expanded(self, margin: Vector3<i32>) -> Volume {
+        let volume = self.make_size_positive();
+
+        Volume {
+            pos1: volume.pos1 - margin,
+            pos2: volume.pos2 + margin,
+        }
+    }
+
     /// Change the position and size of this volume so that it'll contain the vector given
     ///
     /// Considers a vector to be a 1x1x1 cube, not a point
@@ -161,23 +312,29 @@ impl Volume {
     /// assert_eq!(volume.make_size_positive(), Volume::new(Vector3::new(0, 3, -3), Vector3::new(2, 5, 7)))
     /// ```
     pub fn make_size_positive(self) -> Volume {
-        let mut pos1 = self.pos1.into_slice();
-        let mut pos2 = self.pos2.into_slice();
-
-        for i in 0..3 {
-            if pos1[i] > pos2[i] {
-                let tmp = pos1[i];
-                pos1[i] = pos2[i];
-                pos2[i] = tmp;
-            }
-        }
-
         Volume {
-            pos1: Vector3::from_slice(pos1),
-            pos2: Vector3::from_slice(pos2),
+            pos1: self.pos1.min(self.pos2),
+            pos2: self.pos1.max(self.pos2),
         }
     }
 
+    /// Check if the given vector is inside of this volume
+    ///
+    /// ```
+    /// # use litematic_editor::{Volume, Vector3};
+    /// let volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(2, -2, 2));
+    ///
+    /// assert!(volume.contains(Vector3::new(1, -1, 0)));
+    /// assert!(!volume.contains(Vector3::new(1, 0, 0)));
+    /// assert!(!volume.contains(Vector3::new(2, -1, 0)));
+    /// ```
+    pub fn contains(self, vector: Vector3<i32>) -> bool {
+        let volume = self.make_size_positive();
+
+        vector.fits_in_positive(volume.pos1)
+            && vector.fits_in_negative(volume.pos2 - Vector3::new(1, 1, 1))
+    }
+
     /// Get an iterator over every block in the volume, increasing the x, then the z, then the y coordinates
     ///
     /// ```
@@ -198,8 +355,23 @@ impl Volume {
         VolumeIterator {
             volume: self.make_size_positive(),
             current_pos: 0,
+            end_pos: self.volume() as u64,
         }
     }
+
+    /// Get an iterator over every block in the volume in the opposite order of [iter()](Volume::iter), decreasing the x, then the z, then the y coordinates
+    ///
+    /// ```
+    /// # use litematic_editor::{Volume, Vector3};
+    /// let mut iter = Volume::new(Vector3::new(1, 1, 1), Vector3::new(2, 2, 2)).iter_rev();
+    ///
+    /// assert_eq!(iter.next(), Some(Vector3::new(2, 2, 2)));
+    /// assert_eq!(iter.next(), Some(Vector3::new(1, 2, 2)));
+    /// assert_eq!(iter.next(), Some(Vector3::new(2, 2, 1)));
+    /// ```
+    pub fn iter_rev(self) -> impl Iterator<Item = Vector3<i32>> {
+        self.iter().rev()
+    }
 }
 
 impl IntoIterator for Volume {
@@ -211,15 +383,43 @@ impl IntoIterator for Volume {
     }
 }
 
+/// Iterate over every block in the volume, the same as [Volume::iter()]
+///
+/// ```
+/// # use litematic_editor::{Volume, Vector3};
+/// let volume = Volume::new(Vector3::new(0, 0, 0), Vector3::new(2, 1, 2));
+/// let mut count = 0;
+///
+/// for pos in &volume {
+///     assert!(volume.contains(pos));
+///     count += 1;
+/// }
+///
+/// assert_eq!(count, 4);
+/// ```
+impl IntoIterator for &Volume {
+    type Item = IVector3;
+    type IntoIter = VolumeIterator;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 pub struct VolumeIterator {
     volume: Volume,
     current_pos: u64,
+    end_pos: u64,
 }
 
 impl Iterator for VolumeIterator {
     type Item = IVector3;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.current_pos >= self.end_pos {
+            return None;
+        }
+
         let ret = Region::index_to_coords(self.volume.size(), self.current_pos)
             .map(|v| v + self.volume.pos1);
 
@@ -229,6 +429,18 @@ impl Iterator for VolumeIterator {
     }
 }
 
+impl DoubleEndedIterator for VolumeIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current_pos >= self.end_pos {
+            return None;
+        }
+
+        self.end_pos -= 1;
+
+        Region::index_to_coords(self.volume.size(), self.end_pos).map(|v| v + self.volume.pos1)
+    }
+}
+
 impl Default for Volume {
     fn default() -> Self {
         Volume {
@@ -321,4 +533,57 @@ mod tests {
         assert_eq!(iter.next(), Some(Vector3::new(2, 2, 2)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_iter_rev() {
+        let volume = Volume::new(Vector3::new(1, -1, 1), Vector3::new(3, 2, -2));
+
+        let mut forwards: Vec<_> = volume.iter().collect();
+        let backwards: Vec<_> = volume.iter_rev().collect();
+
+        forwards.reverse();
+
+        assert_eq!(backwards.len(), 12);
+        assert_eq!(backwards, forwards);
+
+        let mut iter = volume.iter();
+
+        assert_eq!(iter.next(), Some(Vector3::new(1, -1, -1)));
+        assert_eq!(iter.next_back(), Some(Vector3::new(3, 0, 0)));
+        assert_eq!(iter.by_ref().count(), 10);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_split() {
+        let volume = Volume::new(Vector3::new(5, 2, 3), Vector3::new(-5, 3, -3));
+
+        let pieces = volume.split(Vector3::new(2, 3, 1));
+
+        assert_eq!(pieces.len(), 9);
+        assert_eq!(
+            pieces[0],
+            Volume::new(Vector3::new(0, 2, 0), Vector3::new(2, 3, 1))
+        );
+        assert_eq!(
+            pieces[2],
+            Volume::new(Vector3::new(4, 2, 0), Vector3::new(1, 3, 1))
+        );
+        assert_eq!(
+            pieces[3],
+            Volume::new(Vector3::new(0, 2, 1), Vector3::new(2, 3, 1))
+        );
+
+        let mut covered: Vec<_> = pieces.iter().flat_map(|piece| piece.iter()).collect();
+        let mut expected: Vec<_> = volume.iter().collect();
+
+        covered.sort_by_key(|v| (v.x, v.y, v.z));
+        expected.sort_by_key(|v| (v.x, v.y, v.z));
+
+        assert_eq!(covered, expected);
+
+        assert!(Volume::new(Vector3::new(0, 0, 0), Vector3::new(0, 4, 4))
+            .split(Vector3::new(16, 16, 16))
+            .is_empty());
+    }
 }